@@ -1,7 +1,14 @@
-use syn::{parse::Parse, Ident};
+use syn::{parse::Parse, token, Ident, LitStr};
 
 pub enum FieldAttr {
     OrderBy,
+    SkipInsert,
+    Key,
+    Rename(LitStr),
+    With(syn::ExprPath),
+    Default,
+    Skip,
+    Json,
 }
 
 impl Parse for FieldAttr {
@@ -10,6 +17,24 @@ impl Parse for FieldAttr {
 
         if name == "order_by" {
             return Ok(Self::OrderBy);
+        } else if name == "skip_insert" {
+            return Ok(Self::SkipInsert);
+        } else if name == "key" {
+            return Ok(Self::Key);
+        } else if name == "rename" {
+            input.parse::<token::Eq>()?;
+            let column = input.parse::<LitStr>()?;
+            return Ok(Self::Rename(column));
+        } else if name == "with" {
+            input.parse::<token::Eq>()?;
+            let path = input.parse::<LitStr>()?.parse::<syn::ExprPath>()?;
+            return Ok(Self::With(path));
+        } else if name == "default" {
+            return Ok(Self::Default);
+        } else if name == "skip" {
+            return Ok(Self::Skip);
+        } else if name == "json" {
+            return Ok(Self::Json);
         }
 
         Err(syn::Error::new(input.span(), "invalid attribute `{name}`"))
@@ -4,13 +4,14 @@ use quote::{quote, ToTokens};
 use syn::{self, parse_macro_input, DeriveInput};
 
 mod field_attr;
+mod rename_rule;
 mod struct_attr;
 mod struct_data;
 mod struct_field;
 
-use {field_attr::*, struct_attr::*, struct_data::*, struct_field::*};
+use {field_attr::*, rename_rule::*, struct_attr::*, struct_data::*, struct_field::*};
 
-#[proc_macro_derive(FromRow)]
+#[proc_macro_derive(FromRow, attributes(snowsql))]
 pub fn derive_deserialize(input: TokenStream) -> TokenStream {
     let ast: DeriveInput = parse_macro_input!(input);
 
@@ -51,20 +52,77 @@ pub fn derive_selectable(input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
+#[proc_macro_derive(Insertable, attributes(snowsql))]
+pub fn derive_insertable(input: TokenStream) -> TokenStream {
+    let ast: DeriveInput = parse_macro_input!(input);
+    let struct_data = match StructData::try_from(&ast) {
+        Err(err) => return err.into_compile_error().into(),
+        Ok(sd) => sd,
+    };
+
+    let insertable = impl_insertable(&struct_data);
+
+    #[rustfmt::skip]
+    let expanded = quote! {
+	#insertable
+    };
+
+    TokenStream::from(expanded)
+}
+
+#[proc_macro_derive(Mergeable, attributes(snowsql))]
+pub fn derive_mergeable(input: TokenStream) -> TokenStream {
+    let ast: DeriveInput = parse_macro_input!(input);
+    let struct_data = match StructData::try_from(&ast) {
+        Err(err) => return err.into_compile_error().into(),
+        Ok(sd) => sd,
+    };
+
+    let mergeable = impl_mergeable(&struct_data);
+
+    #[rustfmt::skip]
+    let expanded = quote! {
+	#mergeable
+    };
+
+    TokenStream::from(expanded)
+}
+
 fn impl_from_row(sd: &StructData) -> impl ToTokens {
     let (impl_generics, ty_generics, where_clause) = sd.generics.split_for_impl();
 
     let name = sd.ident;
-    let field_inits = sd.fields.iter().map(|f| f.seq_access_field_init());
+
+    let (seq, body) = if sd.by_name {
+        let field_inits = sd.fields.iter().map(|f| f.named_access_field_init());
+
+        let body = quote! {
+            let row = seq.into_named()?;
+
+            Ok(#name #ty_generics {
+                #(#field_inits),*
+            })
+        };
+
+        (quote! { seq }, body)
+    } else {
+        let field_inits = sd.fields.iter().map(|f| f.seq_access_field_init());
+
+        let body = quote! {
+            Ok(#name #ty_generics {
+                #(#field_inits),*
+            })
+        };
+
+        (quote! { mut seq }, body)
+    };
 
     #[rustfmt::skip]
     quote! {
 	impl #impl_generics snowsql::FromRow for #name #ty_generics #where_clause {
-	    fn from_row<'de, A>(mut seq: snowsql::RowAccess<A>) -> snowsql::FromRowResult<Self>
+	    fn from_row<'de, A>(#seq: snowsql::RowAccess<'_, A>) -> snowsql::FromRowResult<Self>
 	    where A: snowsql::serde::de::SeqAccess<'de> {
-		Ok(#name #ty_generics {
-		    #(#field_inits),*
-                })
+		#body
 	    }
 	}
     }
@@ -79,10 +137,12 @@ fn impl_selectable(sd: &StructData<'_>) -> impl ToTokens {
         panic!("Selectable needs #[snowsql(table_name = \"table_name\")]");
     };
 
-    if sd.fields.iter().any(|f| f.is_order_by) && sd.order_by.is_some() {
+    if let Some(order_by) = sd.order_by.as_ref()
+        && sd.fields.iter().any(|f| f.is_order_by)
+    {
         panic!(
             "Selectable cannot have #[snowsql(order_by = \"{}\")] and col `{}` marked as order_by",
-            sd.order_by.as_ref().unwrap().value(),
+            order_by.value(),
             sd.fields
                 .iter()
                 .find(|f| f.is_order_by)
@@ -92,14 +152,141 @@ fn impl_selectable(sd: &StructData<'_>) -> impl ToTokens {
     }
 
     let order_by = sd.order_by();
-    let field_names = sd.fields.iter().map(|f| f.ident);
+    let select = sd
+        .fields
+        .iter()
+        .filter(|f| !f.skip)
+        .map(|f| f.sql_column())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let by_name = sd.by_name;
+    let columns = sd
+        .fields
+        .iter()
+        .filter(|f| !f.skip)
+        .map(|f| f.column_schema());
 
     #[rustfmt::skip]
     quote! {
 	impl #impl_generics snowsql::Selectable for #name #ty_generics #where_clause {
-            const SELECT: &'static str = stringify!(#(#field_names),*);
+            const SELECT: &'static str = #select;
+            const SCHEMA: snowsql::Schema = snowsql::Schema {
+                columns: &[#(#columns),*],
+                by_name: #by_name,
+            };
             const TABLE_NAME: &'static str = #table_name;
             const ORDER_BY: &'static str = #order_by;
 	}
     }
 }
+
+fn impl_insertable(sd: &StructData<'_>) -> impl ToTokens {
+    let (impl_generics, ty_generics, where_clause) = sd.generics.split_for_impl();
+
+    let name = sd.ident;
+
+    let Some(table_name) = sd.table_name.as_ref() else {
+        panic!("Insertable needs #[snowsql(table_name = \"table_name\")]");
+    };
+
+    let fields = sd
+        .fields
+        .iter()
+        .filter(|f| !f.skip_insert)
+        .collect::<Vec<_>>();
+
+    if fields.is_empty() {
+        panic!("Insertable: all fields are marked with #[snowsql(skip_insert)]");
+    }
+
+    let columns = fields
+        .iter()
+        .map(|f| f.sql_column())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let row = format!("({})", vec!["?"; fields.len()].join(", "));
+
+    // PARSE_JSON is not allowed in a VALUES clause, so json values are parsed selecting from one.
+    let (insert, values_row) = if fields.iter().any(|f| f.is_json) {
+        let source = fields
+            .iter()
+            .enumerate()
+            .map(|(i, f)| f.parsed(&format!("column{}", i + 1)))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let insert = format!(
+            "INSERT INTO {} ({columns}) SELECT {source} FROM VALUES {row}",
+            table_name.value()
+        );
+        (insert, quote! { ::std::option::Option::Some(#row) })
+    } else {
+        let insert = format!(
+            "INSERT INTO {} ({columns}) VALUES {row}",
+            table_name.value()
+        );
+        (insert, quote! { ::std::option::Option::None })
+    };
+    let values = fields.iter().map(|f| f.binding_value());
+
+    #[rustfmt::skip]
+    quote! {
+	impl #impl_generics snowsql::BindRow for #name #ty_generics #where_clause {
+	    fn bind_row(&self) -> ::std::result::Result<::std::vec::Vec<snowsql::BindingValue>, snowsql::BindingError> {
+		::std::result::Result::Ok(::std::vec![#(#values),*])
+	    }
+	}
+
+	impl #impl_generics snowsql::Insertable for #name #ty_generics #where_clause {
+            const INSERT: &'static str = #insert;
+            const INSERT_VALUES_ROW: ::std::option::Option<&'static str> = #values_row;
+	}
+    }
+}
+
+fn impl_mergeable(sd: &StructData<'_>) -> impl ToTokens {
+    let (impl_generics, ty_generics, where_clause) = sd.generics.split_for_impl();
+
+    let name = sd.ident;
+
+    let Some(table_name) = sd.table_name.as_ref() else {
+        panic!("Mergeable needs #[snowsql(table_name = \"table_name\")]");
+    };
+
+    if let Some(field) = sd.fields.iter().find(|f| f.is_key && f.skip_insert) {
+        panic!(
+            "Mergeable: key `{}` cannot be marked with #[snowsql(skip_insert)]",
+            field.ident
+        );
+    }
+
+    let fields = sd
+        .fields
+        .iter()
+        .filter(|f| !f.skip_insert)
+        .collect::<Vec<_>>();
+
+    if !fields.iter().any(|f| f.is_key) {
+        panic!("Mergeable: mark at least one field with #[snowsql(key)]");
+    }
+
+    let columns = fields.iter().map(|f| f.sql_column());
+    let keys = fields.iter().filter(|f| f.is_key).map(|f| f.sql_column());
+    let json_columns = fields.iter().filter(|f| f.is_json).map(|f| f.sql_column());
+    let values = fields.iter().map(|f| f.binding_value());
+
+    #[rustfmt::skip]
+    quote! {
+	impl #impl_generics snowsql::Mergeable for #name #ty_generics #where_clause {
+            const MERGE_TABLE: &'static str = #table_name;
+            const MERGE_COLUMNS: &'static [&'static str] = &[#(#columns),*];
+            const MERGE_KEYS: &'static [&'static str] = &[#(#keys),*];
+            const MERGE_JSON: &'static [&'static str] = &[#(#json_columns),*];
+
+	    fn merge_values(&self) -> ::std::result::Result<::std::vec::Vec<snowsql::BindingValue>, snowsql::BindingError> {
+		::std::result::Result::Ok(::std::vec![#(#values),*])
+	    }
+	}
+    }
+}
@@ -3,6 +3,8 @@ use syn::{parse::Parse, token, Ident, LitStr};
 pub enum StructAttr {
     TableName(LitStr),
     OrderBy(LitStr),
+    RenameAll(LitStr),
+    ByName,
 }
 
 impl Parse for StructAttr {
@@ -17,6 +19,12 @@ impl Parse for StructAttr {
             input.parse::<token::Eq>()?;
             let table_name = input.parse::<LitStr>()?;
             return Ok(Self::OrderBy(table_name));
+        } else if name == "rename_all" {
+            input.parse::<token::Eq>()?;
+            let rule = input.parse::<LitStr>()?;
+            return Ok(Self::RenameAll(rule));
+        } else if name == "by_name" {
+            return Ok(Self::ByName);
         }
 
         Err(syn::Error::new(input.span(), "invalid attribute `{name}`"))
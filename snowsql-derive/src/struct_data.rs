@@ -1,6 +1,6 @@
 use syn::{punctuated::Punctuated, token, Data, DeriveInput, Fields, LitStr};
 
-use super::StructField;
+use super::{RenameRule, StructField};
 
 pub struct StructData<'a> {
     pub ident: &'a syn::Ident,
@@ -8,6 +8,8 @@ pub struct StructData<'a> {
     pub generics: &'a syn::Generics,
     pub table_name: Option<LitStr>,
     pub order_by: Option<LitStr>,
+    /// Fields are matched to columns by name instead of position.
+    pub by_name: bool,
 }
 
 impl<'a> TryFrom<&'a DeriveInput> for StructData<'a> {
@@ -16,6 +18,8 @@ impl<'a> TryFrom<&'a DeriveInput> for StructData<'a> {
     fn try_from(ast: &'a DeriveInput) -> Result<Self, Self::Error> {
         let mut table_name = None::<LitStr>;
         let mut order_by = None::<LitStr>;
+        let mut rename_all = None::<RenameRule>;
+        let mut by_name = false;
 
         for attr in &ast.attrs {
             if attr.path().is_ident("snowsql") {
@@ -25,6 +29,10 @@ impl<'a> TryFrom<&'a DeriveInput> for StructData<'a> {
                     match snowflake_attr {
                         super::StructAttr::TableName(name) => table_name = Some(name),
                         super::StructAttr::OrderBy(col) => order_by = Some(col),
+                        super::StructAttr::ByName => by_name = true,
+                        super::StructAttr::RenameAll(rule) => {
+                            rename_all = Some(RenameRule::from_lit(&rule)?)
+                        }
                     }
                 }
             }
@@ -35,7 +43,7 @@ impl<'a> TryFrom<&'a DeriveInput> for StructData<'a> {
                 Fields::Named(data) => {
                     let mut fields = Vec::with_capacity(data.named.len());
                     for (i, field) in data.named.iter().enumerate() {
-                        fields.push(StructField::from_index_and_field(i, field)?);
+                        fields.push(StructField::from_index_and_field(i, field, rename_all)?);
                     }
 
                     fields
@@ -52,6 +60,7 @@ impl<'a> TryFrom<&'a DeriveInput> for StructData<'a> {
             generics: &ast.generics,
             table_name,
             order_by,
+            by_name,
         })
     }
 }
@@ -71,13 +80,16 @@ impl StructData<'_> {
         if let Some(order_by) = self.order_by.as_ref() {
             let name = order_by.value();
 
-            if self.fields.iter().all(|f| f.ident != name.as_str()) {
-                panic!("Selectable: order_by column `{name}` not found");
+            match self
+                .fields
+                .iter()
+                .find(|f| f.ident == name.as_str() || f.column == name)
+            {
+                Some(field) => field.sql_column(),
+                None => panic!("Selectable: order_by column `{name}` not found"),
             }
-
-            name
         } else if let Some(field) = self.fields.iter().find(|f| f.is_order_by) {
-            field.ident.to_string()
+            field.sql_column()
         } else {
             panic!("Selectable: add #[snowsql(order_by = \"col\")] to struct or mark a field with #[snowsql(order_by)]")
         }
@@ -1,17 +1,42 @@
 use quote::{quote, ToTokens};
-use syn::{punctuated::Punctuated, token};
+use syn::{ext::IdentExt, punctuated::Punctuated, token};
+
+use super::RenameRule;
 
 pub struct StructField<'a> {
     pub ident: &'a syn::Ident,
     #[allow(dead_code)]
     pub index: usize,
     pub typ: &'a syn::Type,
+    /// Snowflake column name, after `rename` and `rename_all`.
+    pub column: String,
+    /// The column was named with `rename` or `rename_all`.
+    pub is_renamed: bool,
     pub is_order_by: bool,
+    pub skip_insert: bool,
+    pub is_key: bool,
+    pub with: Option<syn::ExprPath>,
+    pub is_default: bool,
+    /// Not a column at all, initialized with `Default::default()`.
+    pub skip: bool,
+    /// Deserialized with serde from a JSON column, bound through `PARSE_JSON(?)`.
+    pub is_json: bool,
 }
 
 impl<'a> StructField<'a> {
-    pub fn from_index_and_field(index: usize, field: &'a syn::Field) -> Result<Self, syn::Error> {
+    pub fn from_index_and_field(
+        index: usize,
+        field: &'a syn::Field,
+        rename_all: Option<RenameRule>,
+    ) -> Result<Self, syn::Error> {
         let mut is_order_by = false;
+        let mut skip_insert = false;
+        let mut is_key = false;
+        let mut rename = None;
+        let mut with = None;
+        let mut is_default = false;
+        let mut skip = false;
+        let mut is_json = false;
 
         for attr in &field.attrs {
             if attr.path().is_ident("snowsql") {
@@ -20,23 +45,163 @@ impl<'a> StructField<'a> {
                 )? {
                     match snowflake_attr {
                         super::FieldAttr::OrderBy => is_order_by = true,
+                        super::FieldAttr::SkipInsert => skip_insert = true,
+                        super::FieldAttr::Key => is_key = true,
+                        super::FieldAttr::Rename(column) => rename = Some(column.value()),
+                        super::FieldAttr::With(path) => with = Some(path),
+                        super::FieldAttr::Default => is_default = true,
+                        super::FieldAttr::Skip => skip = true,
+                        super::FieldAttr::Json => is_json = true,
                     }
                 }
             }
         }
 
+        let ident = field.ident.as_ref().unwrap();
+        let is_renamed = rename.is_some() || rename_all.is_some();
+
+        let column = rename.unwrap_or_else(|| {
+            let name = ident.unraw().to_string();
+
+            match rename_all {
+                Some(rule) => rule.apply(&name),
+                None => name,
+            }
+        });
+
         Ok(Self {
-            ident: field.ident.as_ref().unwrap(),
+            ident,
             index,
             typ: &field.ty,
+            column,
+            is_renamed,
             is_order_by,
+            skip_insert: skip_insert || skip,
+            is_key,
+            with,
+            is_default,
+            skip,
+            is_json,
         })
     }
 
+    /// The column as written in statements.
+    ///
+    /// Snowflake upper-cases unquoted names, so renamed columns are quoted
+    /// unless they are already upper-case, e.g. `"DisplayName"` and `"my_col"` but `MY_COL`.
+    pub fn sql_column(&self) -> String {
+        let column = &self.column;
+
+        let is_upper_case = !column.chars().any(|c| c.is_ascii_lowercase());
+        let is_identifier = column
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && column
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+
+        if self.is_renamed && !(is_upper_case && is_identifier) {
+            format!("\"{}\"", column.replace('"', "\"\""))
+        } else {
+            column.clone()
+        }
+    }
+
+    /// The function parsing the column instead of `FromValue`, if any.
+    fn parser(&self) -> Option<impl ToTokens> {
+        match &self.with {
+            Some(with) => Some(quote! { #with }),
+            None if self.is_json => Some(quote! { snowsql::from_json }),
+            None => None,
+        }
+    }
+
     pub fn seq_access_field_init(&self) -> impl ToTokens {
         let ident = self.ident;
         let typ = self.typ;
+        let column = &self.column;
+
+        match (self.parser(), self.is_default, self.skip) {
+            (_, _, true) => quote! { #ident: ::std::default::Default::default() },
+            (Some(with), true, _) => quote! { #ident: seq.next_or_default_with(#column, #with)? },
+            (Some(with), false, _) => quote! { #ident: seq.next_with(#column, #with)? },
+            (None, true, _) => quote! { #ident: seq.next_or_default::<#typ>(#column)? },
+            (None, false, _) => quote! { #ident: seq.next::<#typ>(#column)? },
+        }
+    }
+
+    pub fn named_access_field_init(&self) -> impl ToTokens {
+        let ident = self.ident;
+        let typ = self.typ;
+        let column = &self.column;
 
-        quote! { #ident: seq.next::<#typ>(stringify!(#ident))? }
+        match (self.parser(), self.is_default, self.skip) {
+            (_, _, true) => quote! { #ident: ::std::default::Default::default() },
+            (Some(with), true, _) => quote! { #ident: row.get_or_default_with(#column, #with)? },
+            (Some(with), false, _) => quote! { #ident: row.get_with(#column, #with)? },
+            (None, true, _) => quote! { #ident: row.get_or_default::<#typ>(#column)? },
+            (None, false, _) => quote! { #ident: row.get::<#typ>(#column)? },
+        }
+    }
+
+    pub fn column_schema(&self) -> impl ToTokens {
+        let typ = self.typ;
+        let column = &self.column;
+
+        let (nullable, accepts) = match (&self.with, self.is_default) {
+            (Some(_), _) => (quote! { true }, quote! { |_| true }),
+            (None, is_default) if self.is_json => {
+                let nullable = is_default || self.is_option();
+                (quote! { #nullable }, quote! { snowsql::accepts_json })
+            }
+            (None, true) => (
+                quote! { true },
+                quote! { <#typ as snowsql::FromValue>::accepts },
+            ),
+            (None, false) => (
+                quote! { <#typ as snowsql::FromValue>::NULLABLE },
+                quote! { <#typ as snowsql::FromValue>::accepts },
+            ),
+        };
+
+        quote! {
+            snowsql::ColumnSchema {
+                name: #column,
+                nullable: #nullable,
+                accepts: #accepts,
+            }
+        }
+    }
+
+    /// Whether the field is an `Option`, which `json` fields read null columns into.
+    fn is_option(&self) -> bool {
+        match self.typ {
+            syn::Type::Path(path) => path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "Option"),
+            _ => false,
+        }
+    }
+
+    pub fn binding_value(&self) -> impl ToTokens {
+        let ident = self.ident;
+
+        if self.is_json {
+            quote! { snowsql::BindingValue::try_from(snowsql::Json(::std::clone::Clone::clone(&self.#ident)))? }
+        } else {
+            quote! { snowsql::BindingValue::from(::std::clone::Clone::clone(&self.#ident)) }
+        }
+    }
+
+    /// `value` as stored in the field's column, parsed with `PARSE_JSON` for `json` fields.
+    pub fn parsed(&self, value: &str) -> String {
+        if self.is_json {
+            format!("PARSE_JSON({value})")
+        } else {
+            value.to_string()
+        }
     }
 }
@@ -20,10 +20,27 @@ pub enum BindingValue {
     Double(f64),
     Char(char),
     String(String),
-    // Decimal(Decimal),
-    // DateTime(NaiveDateTime),
-    // Date(NaiveDate),
-    // Time(NaiveTime),
+
+    /// A null value, typed so Snowflake knows the column type.
+    Null(BindingKind),
+    /// An exact decimal number, see the `rust_decimal` feature.
+    Decimal(String),
+
+    // Dates and times are kept formatted as Snowflake expects them,
+    // see the `From` impls of each datetime feature.
+    /// `YYYY-MM-DD`
+    Date(String),
+    /// `HH:MI:SS.FFFFFFFFF`
+    Time(String),
+    /// `YYYY-MM-DD HH:MI:SS.FFFFFFFFF`
+    TimestampNtz(String),
+    /// `YYYY-MM-DD HH:MI:SS.FFFFFFFFF +TZH:TZM`
+    TimestampLtz(String),
+    /// `YYYY-MM-DD HH:MI:SS.FFFFFFFFF +TZH:TZM`
+    TimestampTz(String),
+
+    /// JSON text, see [`crate::Json`].
+    Json(String),
 }
 
 impl BindingValue {
@@ -42,16 +59,35 @@ impl BindingValue {
             | BindingValue::USize(_) => BindingKind::Fixed,
 
             BindingValue::Float(_) | BindingValue::Double(_) => BindingKind::Real,
-            BindingValue::Char(_) | BindingValue::String(_) => BindingKind::Text,
-            // BindingValue::Decimal(_) => BindingKind::Real,
-            // BindingValue::DateTime(_) => BindingKind::DateTime,
-            // BindingValue::Date(_) => BindingKind::Date,
-            // BindingValue::Time(_) => BindingKind::Time,
+            BindingValue::Char(_) | BindingValue::String(_) | BindingValue::Json(_) => {
+                BindingKind::Text
+            }
+            BindingValue::Null(kind) => *kind,
+            BindingValue::Decimal(_) => BindingKind::Fixed,
+            BindingValue::Date(_) => BindingKind::Date,
+            BindingValue::Time(_) => BindingKind::Time,
+            BindingValue::TimestampNtz(_) => BindingKind::TimestampNtz,
+            BindingValue::TimestampLtz(_) => BindingKind::TimestampLtz,
+            BindingValue::TimestampTz(_) => BindingKind::TimestampTz,
+        }
+    }
+
+    /// The value as sent to Snowflake, `None` for null.
+    pub fn value(&self) -> Option<String> {
+        match self {
+            BindingValue::Null(_) => None,
+            v => Some(v.to_string()),
         }
     }
 }
 
-#[derive(Clone, Copy, Debug, serde::Serialize)]
+/// Types that always bind as the same [`BindingKind`],
+/// which allows binding `None` as a typed null.
+pub trait BindingType: Into<BindingValue> {
+    const KIND: BindingKind;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum BindingKind {
     Bool,
@@ -61,8 +97,15 @@ pub enum BindingKind {
     DateTime,
     Date,
     Time,
+    TimestampNtz,
+    TimestampLtz,
+    TimestampTz,
 }
 
+/// Binds a timestamp with time zone as `TIMESTAMP_LTZ` rather than `TIMESTAMP_TZ`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimestampLtz<T>(pub T);
+
 impl std::fmt::Display for BindingValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -81,6 +124,14 @@ impl std::fmt::Display for BindingValue {
             BindingValue::Double(v) => <f64 as fmt::Display>::fmt(v, f),
             BindingValue::Char(v) => <char as fmt::Display>::fmt(v, f),
             BindingValue::String(v) => <String as fmt::Display>::fmt(v, f),
+            BindingValue::Null(_) => f.write_str("NULL"),
+            BindingValue::Date(v)
+            | BindingValue::Time(v)
+            | BindingValue::TimestampNtz(v)
+            | BindingValue::TimestampLtz(v)
+            | BindingValue::TimestampTz(v)
+            | BindingValue::Decimal(v)
+            | BindingValue::Json(v) => <String as fmt::Display>::fmt(v, f),
         }
     }
 }
@@ -91,32 +142,48 @@ impl From<&str> for BindingValue {
     }
 }
 
+impl BindingType for &str {
+    const KIND: BindingKind = BindingKind::Text;
+}
+
+impl<T> From<Option<T>> for BindingValue
+where
+    T: BindingType,
+{
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(v) => v.into(),
+            None => BindingValue::Null(T::KIND),
+        }
+    }
+}
+
 macro_rules! impl_from_binding_value {
-    ($ty: ty, $ex: expr) => {
+    ($ty: ty, $ex: expr, $kind: expr) => {
         impl From<$ty> for BindingValue {
             fn from(value: $ty) -> Self {
                 $ex(value)
             }
         }
+
+        impl BindingType for $ty {
+            const KIND: BindingKind = $kind;
+        }
     };
 }
-impl_from_binding_value!(bool, BindingValue::Bool);
-impl_from_binding_value!(i8, BindingValue::Byte);
-impl_from_binding_value!(i16, BindingValue::SmallInt);
-impl_from_binding_value!(i32, BindingValue::Int);
-impl_from_binding_value!(i64, BindingValue::BigInt);
-impl_from_binding_value!(isize, BindingValue::ISize);
-impl_from_binding_value!(u8, BindingValue::UByte);
-impl_from_binding_value!(u16, BindingValue::SmallUInt);
-impl_from_binding_value!(u32, BindingValue::UInt);
-impl_from_binding_value!(u64, BindingValue::BigUInt);
-impl_from_binding_value!(usize, BindingValue::USize);
-impl_from_binding_value!(f32, BindingValue::Float);
-impl_from_binding_value!(f64, BindingValue::Double);
-impl_from_binding_value!(char, BindingValue::Char);
-impl_from_binding_value!(String, BindingValue::String);
-
-//impl_from_binding_value!(Decimal, BindingValue::Decimal);
-//impl_from_binding_value!(NaiveDateTime, BindingValue::DateTime);
-//impl_from_binding_value!(NaiveDate, BindingValue::Date);
-//impl_from_binding_value!(NaiveTime, BindingValue::Time);
+impl_from_binding_value!(bool, BindingValue::Bool, BindingKind::Bool);
+impl_from_binding_value!(i8, BindingValue::Byte, BindingKind::Fixed);
+impl_from_binding_value!(i16, BindingValue::SmallInt, BindingKind::Fixed);
+impl_from_binding_value!(i32, BindingValue::Int, BindingKind::Fixed);
+impl_from_binding_value!(i64, BindingValue::BigInt, BindingKind::Fixed);
+impl_from_binding_value!(isize, BindingValue::ISize, BindingKind::Fixed);
+impl_from_binding_value!(u8, BindingValue::UByte, BindingKind::Fixed);
+impl_from_binding_value!(u16, BindingValue::SmallUInt, BindingKind::Fixed);
+impl_from_binding_value!(u32, BindingValue::UInt, BindingKind::Fixed);
+impl_from_binding_value!(u64, BindingValue::BigUInt, BindingKind::Fixed);
+impl_from_binding_value!(usize, BindingValue::USize, BindingKind::Fixed);
+impl_from_binding_value!(f32, BindingValue::Float, BindingKind::Real);
+impl_from_binding_value!(f64, BindingValue::Double, BindingKind::Real);
+impl_from_binding_value!(char, BindingValue::Char, BindingKind::Text);
+impl_from_binding_value!(String, BindingValue::String, BindingKind::Text);
+
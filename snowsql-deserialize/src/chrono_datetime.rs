@@ -0,0 +1,237 @@
+use chrono::{
+    DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, TimeZone, Utc,
+};
+
+use crate::{
+    epoch, BindingKind, BindingType, BindingValue, Error, FromValue, RowType, SnowflakeType,
+    TimestampLtz,
+};
+
+/// Parsed as the number of days since the epoch, like the `time` impl.
+impl FromValue for NaiveDate {
+    fn from_value(s: &str) -> Result<Self, Error> {
+        let days = epoch::parse_days(s)?;
+
+        TimeDelta::try_days(days)
+            .and_then(|days| DateTime::UNIX_EPOCH.date_naive().checked_add_signed(days))
+            .ok_or_else(|| Error::Value {
+                given: s.into(),
+                err: format!("value to large. `{days}` days"),
+            })
+    }
+
+    fn accepts(column: &RowType) -> bool {
+        column.snowflake_type == SnowflakeType::Date
+    }
+}
+
+/// TIME, decoded as the time since midnight, see [`epoch::parse_time`].
+impl FromValue for NaiveTime {
+    fn from_value(s: &str) -> Result<Self, Error> {
+        let (secs, nanos) = epoch::parse_time(s)?;
+
+        NaiveTime::from_num_seconds_from_midnight_opt(secs, nanos).ok_or_else(|| Error::Value {
+            given: s.into(),
+            err: "expected the number of seconds since midnight".into(),
+        })
+    }
+
+    fn accepts(column: &RowType) -> bool {
+        matches!(column.snowflake_type, SnowflakeType::Time { .. })
+    }
+}
+
+/// TIMESTAMP_NTZ, the wall clock date and time.
+///
+/// A `TIMESTAMP_TZ` value gives its local date and time.
+impl FromValue for NaiveDateTime {
+    fn from_value(s: &str) -> Result<Self, Error> {
+        DateTime::<FixedOffset>::from_value(s).map(|dt| dt.naive_local())
+    }
+
+    fn accepts(column: &RowType) -> bool {
+        matches!(column.snowflake_type, SnowflakeType::TimestampNtz { .. })
+    }
+}
+
+/// TIMESTAMP_LTZ and TIMESTAMP_TZ, see [`epoch::parse_timestamp`].
+///
+/// Values without a time zone offset are UTC.
+impl FromValue for DateTime<FixedOffset> {
+    fn from_value(s: &str) -> Result<Self, Error> {
+        let timestamp = epoch::parse_timestamp(s)?;
+
+        let utc_time =
+            DateTime::from_timestamp(timestamp.secs, timestamp.nanos).ok_or_else(|| {
+                Error::Value {
+                    given: s.into(),
+                    err: format!("value to large. `{}` seconds", timestamp.secs),
+                }
+            })?;
+
+        let Some(offset_minutes) = timestamp.offset_minutes else {
+            return Ok(utc_time.fixed_offset());
+        };
+
+        FixedOffset::east_opt(offset_minutes * 60)
+            .map(|offset| utc_time.with_timezone(&offset))
+            .ok_or_else(|| Error::Value {
+                given: s.into(),
+                err: format!("invalid offset in minutes `{offset_minutes}`"),
+            })
+    }
+
+    fn accepts(column: &RowType) -> bool {
+        is_instant(column)
+    }
+}
+
+impl FromValue for DateTime<Utc> {
+    fn from_value(s: &str) -> Result<Self, Error> {
+        DateTime::<FixedOffset>::from_value(s).map(|dt| dt.to_utc())
+    }
+
+    fn accepts(column: &RowType) -> bool {
+        is_instant(column)
+    }
+}
+
+fn is_instant(column: &RowType) -> bool {
+    matches!(
+        column.snowflake_type,
+        SnowflakeType::TimestampLtz { .. } | SnowflakeType::TimestampTz { .. }
+    )
+}
+
+fn format_offset_date_time<Tz>(dt: &DateTime<Tz>) -> String
+where
+    Tz: TimeZone,
+    Tz::Offset: std::fmt::Display,
+{
+    dt.format("%Y-%m-%d %H:%M:%S%.9f %:z").to_string()
+}
+
+impl From<NaiveDate> for BindingValue {
+    fn from(value: NaiveDate) -> Self {
+        BindingValue::Date(value.format("%Y-%m-%d").to_string())
+    }
+}
+
+impl BindingType for NaiveDate {
+    const KIND: BindingKind = BindingKind::Date;
+}
+
+impl From<NaiveTime> for BindingValue {
+    fn from(value: NaiveTime) -> Self {
+        BindingValue::Time(value.format("%H:%M:%S%.9f").to_string())
+    }
+}
+
+impl BindingType for NaiveTime {
+    const KIND: BindingKind = BindingKind::Time;
+}
+
+impl From<NaiveDateTime> for BindingValue {
+    fn from(value: NaiveDateTime) -> Self {
+        BindingValue::TimestampNtz(value.format("%Y-%m-%d %H:%M:%S%.9f").to_string())
+    }
+}
+
+impl BindingType for NaiveDateTime {
+    const KIND: BindingKind = BindingKind::TimestampNtz;
+}
+
+/// Keeps the offset, binding as `TIMESTAMP_TZ`.
+impl<Tz> From<DateTime<Tz>> for BindingValue
+where
+    Tz: TimeZone,
+    Tz::Offset: std::fmt::Display,
+{
+    fn from(value: DateTime<Tz>) -> Self {
+        BindingValue::TimestampTz(format_offset_date_time(&value))
+    }
+}
+
+impl<Tz> BindingType for DateTime<Tz>
+where
+    Tz: TimeZone,
+    Tz::Offset: std::fmt::Display,
+{
+    const KIND: BindingKind = BindingKind::TimestampTz;
+}
+
+impl<Tz> From<TimestampLtz<DateTime<Tz>>> for BindingValue
+where
+    Tz: TimeZone,
+    Tz::Offset: std::fmt::Display,
+{
+    fn from(value: TimestampLtz<DateTime<Tz>>) -> Self {
+        BindingValue::TimestampLtz(format_offset_date_time(&value.0))
+    }
+}
+
+impl<Tz> BindingType for TimestampLtz<DateTime<Tz>>
+where
+    Tz: TimeZone,
+    Tz::Offset: std::fmt::Display,
+{
+    const KIND: BindingKind = BindingKind::TimestampLtz;
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn date(year: i32, month: u8, day: u8) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month.into(), day.into()).unwrap()
+    }
+
+    fn datetime(
+        date: (i32, u8, u8),
+        time: (u8, u8, u8, u32),
+        offset_seconds: i32,
+    ) -> DateTime<FixedOffset> {
+        FixedOffset::east_opt(offset_seconds)
+            .unwrap()
+            .from_local_datetime(&naive(date, time))
+            .unwrap()
+    }
+
+    fn time(hour: u8, minute: u8, second: u8, nanos: u32) -> NaiveTime {
+        NaiveTime::from_hms_nano_opt(hour.into(), minute.into(), second.into(), nanos).unwrap()
+    }
+
+    fn naive(
+        (year, month, day): (i32, u8, u8),
+        (hour, minute, second, nanos): (u8, u8, u8, u32),
+    ) -> NaiveDateTime {
+        date(year, month, day).and_time(time(hour, minute, second, nanos))
+    }
+
+    crate::epoch::datetime_tests!(
+        date: date -> NaiveDate,
+        time: time -> NaiveTime,
+        naive: naive -> NaiveDateTime,
+        datetime: datetime -> DateTime<FixedOffset>,
+    );
+
+    #[test]
+    fn deserialize_utc() {
+        assert_eq!(
+            Utc.with_ymd_and_hms(2021, 3, 19, 17, 6, 59).unwrap(),
+            DateTime::<Utc>::from_value("1616173619.000000000 960").expect("deserializing")
+        );
+    }
+
+    #[test]
+    fn bind_utc() {
+        let value = BindingValue::from(Utc.with_ymd_and_hms(2024, 2, 6, 14, 5, 30).unwrap());
+        assert!(matches!(value.kind(), BindingKind::TimestampTz));
+        assert_eq!(value.to_string(), "2024-02-06 14:05:30.000000000 +00:00");
+
+        let value = BindingValue::from(None::<DateTime<Utc>>);
+        assert!(matches!(value.kind(), BindingKind::TimestampTz));
+        assert_eq!(value.value(), None);
+    }
+}
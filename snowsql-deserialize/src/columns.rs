@@ -0,0 +1,59 @@
+use crate::SnowflakeType;
+
+/// Metadata of a result column, from `resultSetMetaData.rowType`.
+#[derive(serde::Deserialize, Clone, Debug)]
+#[serde(from = "RawRowType")]
+pub struct RowType {
+    pub name: String,
+    pub database: String,
+    pub schema: String,
+    pub table: String,
+    pub precision: Option<u32>,
+    pub byte_length: Option<usize>,
+    pub length: Option<usize>,
+    pub data_type: String,
+    pub scale: Option<i32>,
+    pub nullable: bool,
+    /// `data_type` parsed along with its precision, scale and length.
+    pub snowflake_type: SnowflakeType,
+    //pub collation: ???,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawRowType {
+    name: String,
+    database: String,
+    schema: String,
+    table: String,
+    precision: Option<u32>,
+    byte_length: Option<usize>,
+    length: Option<usize>,
+    #[serde(rename = "type")]
+    data_type: String,
+    scale: Option<i32>,
+    nullable: bool,
+}
+
+impl From<RawRowType> for RowType {
+    fn from(raw: RawRowType) -> Self {
+        Self {
+            snowflake_type: SnowflakeType::parse(
+                &raw.data_type,
+                raw.precision,
+                raw.scale,
+                raw.length,
+            ),
+            name: raw.name,
+            database: raw.database,
+            schema: raw.schema,
+            table: raw.table,
+            precision: raw.precision,
+            byte_length: raw.byte_length,
+            length: raw.length,
+            data_type: raw.data_type,
+            scale: raw.scale,
+            nullable: raw.nullable,
+        }
+    }
+}
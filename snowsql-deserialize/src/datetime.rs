@@ -1,7 +1,7 @@
-use crate::{Error, FromValue};
-
-// static DATETIME_TZ_FORMAT: &[FormatItem<'_>] =
-//     time::macros::format_description!("[unix_timestamp][ignore count:1][optional [subsecond]]");
+use crate::{
+    epoch, BindingKind, BindingType, BindingValue, Error, FromValue, RowType, SnowflakeType,
+    TimestampLtz,
+};
 
 // DATE
 //     Integer value (in a string) of the number of days since the epoch (e.g. 18262).
@@ -15,177 +15,218 @@ impl FromValue for time::Date {
     where
         Self: Sized,
     {
-        // days since epoch.
-
-        let days = s.parse::<i64>().map_err(|_| Error::Format {
-            given: s.into(),
-            err: "time::Date expects a string with the number of days since epoch.".into(),
-        })?;
+        let days = epoch::parse_days(s)?;
 
         let date = time::OffsetDateTime::UNIX_EPOCH.date() + time::Duration::days(days);
 
         Ok(date)
     }
+
+    fn accepts(column: &RowType) -> bool {
+        column.snowflake_type == SnowflakeType::Date
+    }
 }
 
+/// TIME, decoded as the time since midnight, see [`epoch::parse_time`].
+impl FromValue for time::Time {
+    fn from_value(s: &str) -> Result<Self, Error> {
+        let (secs, nanos) = epoch::parse_time(s)?;
+
+        time::Time::from_hms_nano(
+            (secs / 3600) as u8,
+            (secs / 60 % 60) as u8,
+            (secs % 60) as u8,
+            nanos,
+        )
+        .map_err(|err| Error::Value {
+            given: s.into(),
+            err: err.to_string(),
+        })
+    }
+
+    fn accepts(column: &RowType) -> bool {
+        matches!(column.snowflake_type, SnowflakeType::Time { .. })
+    }
+}
+
+/// TIMESTAMP_NTZ, the wall clock date and time.
 ///
-/// TIME, TIMESTAMP_LTZ, TIMESTAMP_NTZ
-///     Float value (with 9 decimal places) of the number of seconds since the epoch (e.g. 82919.000000000).
-///
-///
-/// TIMESTAMP_TZ
-///     Float value (with 9 decimal places) of the number of seconds since the epoch,
-///     followed by a space and the time zone offset in minutes (e.g. 1616173619000000000 960)
+/// A `TIMESTAMP_TZ` value gives its local date and time.
+impl FromValue for time::PrimitiveDateTime {
+    fn from_value(s: &str) -> Result<Self, Error> {
+        time::OffsetDateTime::from_value(s)
+            .map(|dt| time::PrimitiveDateTime::new(dt.date(), dt.time()))
+    }
+
+    fn accepts(column: &RowType) -> bool {
+        matches!(column.snowflake_type, SnowflakeType::TimestampNtz { .. })
+    }
+}
+
+/// TIMESTAMP_LTZ and TIMESTAMP_TZ, see [`epoch::parse_timestamp`].
 ///
+/// Values without a time zone offset are UTC.
 impl FromValue for time::OffsetDateTime {
     fn from_value(s: &str) -> Result<Self, Error>
     where
         Self: Sized,
     {
-        // If TIMEZONE is missing, we expect the Date to be UTC.
+        let timestamp = epoch::parse_timestamp(s)?;
 
-        // if the string contains a space. The timezone offset (in minutes)
-        // follows..
-        if s.contains(' ') {
-            // TIMESTAMP_TZ
-            //     Float value (with 9 decimal places) of the number of seconds since the epoch,
-            //     followed by a space and the time zone offset in minutes (e.g. 1616173619000000000 960)
-            //
-
-            let (dt_part, offset_part) = s.split_once(' ').unwrap();
+        let utc_time = time::OffsetDateTime::from_unix_timestamp(timestamp.secs)
+            .and_then(|dt| dt.replace_nanosecond(timestamp.nanos))
+            .map_err(|err| Error::Value {
+                given: s.into(),
+                err: err.to_string(),
+            })?;
 
-            let utc_time = parse_utc(dt_part)?;
+        let Some(offset_minutes) = timestamp.offset_minutes else {
+            return Ok(utc_time);
+        };
 
-            let offset_minutes = offset_part.parse::<i32>().map_err(|err| Error::Format {
+        time::UtcOffset::from_whole_seconds(offset_minutes * 60)
+            .ok()
+            .and_then(|offset| utc_time.checked_to_offset(offset))
+            .ok_or_else(|| Error::Value {
                 given: s.into(),
-                err: format!("parsing Offset minutes from `{offset_part}`: {err}"),
-            })?;
+                err: format!("invalid offset in minutes `{offset_minutes}`"),
+            })
+    }
 
-            // for some reason all dates received from Snowsql are marked as +1440 minutes (24h)
-            // So we do modulo 24 on the offset..... This is probably wrong but I see no other solution.
+    fn accepts(column: &RowType) -> bool {
+        matches!(
+            column.snowflake_type,
+            SnowflakeType::TimestampLtz { .. } | SnowflakeType::TimestampTz { .. }
+        )
+    }
+}
+
+fn format_date(d: time::Date) -> String {
+    format!("{:04}-{:02}-{:02}", d.year(), u8::from(d.month()), d.day())
+}
 
-            let offset = time::UtcOffset::from_whole_seconds((offset_minutes % 1440) * 60)
-                .map_err(|err| Error::Value {
-                    given: s.into(),
-                    err: format!("invalid offset in minutes `{offset_minutes}`: {err}"),
-                })?;
+fn format_time(t: time::Time) -> String {
+    format!(
+        "{:02}:{:02}:{:02}.{:09}",
+        t.hour(),
+        t.minute(),
+        t.second(),
+        t.nanosecond()
+    )
+}
+
+fn format_offset(o: time::UtcOffset) -> String {
+    let minutes = o.whole_minutes();
+    let sign = if minutes < 0 { '-' } else { '+' };
+
+    format!("{sign}{:02}:{:02}", minutes.abs() / 60, minutes.abs() % 60)
+}
 
-            Ok(utc_time.replace_offset(offset))
-        } else {
-            parse_utc(s)
-        }
+fn format_offset_date_time(dt: time::OffsetDateTime) -> String {
+    format!(
+        "{} {} {}",
+        format_date(dt.date()),
+        format_time(dt.time()),
+        format_offset(dt.offset())
+    )
+}
+
+impl From<time::Date> for BindingValue {
+    fn from(value: time::Date) -> Self {
+        BindingValue::Date(format_date(value))
     }
 }
 
-fn parse_utc(s: &str) -> Result<time::OffsetDateTime, Error> {
-    let max_seconds: i64 = (time::PrimitiveDateTime::MAX.assume_utc()
-        - time::OffsetDateTime::UNIX_EPOCH)
-        .whole_seconds();
-
-    let mut parts = s.split('.');
-
-    let secs_s = parts.next().ok_or_else(|| Error::Format {
-        given: s.into(),
-        err: "invalid DateTime".into(),
-    })?;
-
-    let parsed_secs = secs_s.parse::<i64>().map_err(|err| Error::Format {
-        given: s.into(),
-        err: format!("parsing seconds from `{secs_s}`: {err}"),
-    })?;
-
-    let secs = if max_seconds < parsed_secs {
-        max_seconds
-    } else {
-        parsed_secs
-    };
-
-    let nanos = parts
-        .next()
-        .map(|subsecs_s| {
-            subsecs_s.parse::<i64>().map_err(|err| Error::Format {
-                given: s.into(),
-                err: format!("parsing subseconds from `{subsecs_s}`: {err}"),
-            })
-        })
-        .transpose()?
-        .unwrap_or(0);
+impl BindingType for time::Date {
+    const KIND: BindingKind = BindingKind::Date;
+}
 
-    let res = time::OffsetDateTime::UNIX_EPOCH
-        .checked_add(time::Duration::seconds(secs))
-        .ok_or_else(|| Error::Format {
-            given: s.into(),
-            err: format!("value to large. `{secs}` seconds"),
-        })?
-        .checked_add(time::Duration::nanoseconds(nanos))
-        .ok_or_else(|| Error::Format {
-            given: s.into(),
-            err: format!("value to large. `{nanos}` nanoseconds"),
-        })?;
+impl From<time::Time> for BindingValue {
+    fn from(value: time::Time) -> Self {
+        BindingValue::Time(format_time(value))
+    }
+}
+
+impl BindingType for time::Time {
+    const KIND: BindingKind = BindingKind::Time;
+}
+
+impl From<time::PrimitiveDateTime> for BindingValue {
+    fn from(value: time::PrimitiveDateTime) -> Self {
+        BindingValue::TimestampNtz(format!(
+            "{} {}",
+            format_date(value.date()),
+            format_time(value.time())
+        ))
+    }
+}
+
+impl BindingType for time::PrimitiveDateTime {
+    const KIND: BindingKind = BindingKind::TimestampNtz;
+}
+
+/// Keeps the offset, binding as `TIMESTAMP_TZ`.
+impl From<time::OffsetDateTime> for BindingValue {
+    fn from(value: time::OffsetDateTime) -> Self {
+        BindingValue::TimestampTz(format_offset_date_time(value))
+    }
+}
+
+impl BindingType for time::OffsetDateTime {
+    const KIND: BindingKind = BindingKind::TimestampTz;
+}
+
+impl From<TimestampLtz<time::OffsetDateTime>> for BindingValue {
+    fn from(value: TimestampLtz<time::OffsetDateTime>) -> Self {
+        BindingValue::TimestampLtz(format_offset_date_time(value.0))
+    }
+}
 
-    Ok(res)
+impl BindingType for TimestampLtz<time::OffsetDateTime> {
+    const KIND: BindingKind = BindingKind::TimestampLtz;
 }
 
 #[cfg(test)]
 mod tests {
 
     use super::*;
-    use time::macros::datetime;
 
-    #[test]
-    fn deserialize_date_time() {
-        assert_eq!(
-            datetime!(2024-10-17 12:03:22.422528 UTC),
-            time::OffsetDateTime::from_value("1729166602.422528000").expect("deserializing")
-        );
-
-        assert_eq!(
-            datetime!(2024-10-18 10:54:22.912451 UTC),
-            time::OffsetDateTime::from_value("1729248862.912451000").expect("deserializing")
-        );
-
-        assert_eq!(
-            datetime!(2024-02-06 14:05:30 +0000),
-            time::OffsetDateTime::from_value("1707228330 1440").expect("deserializing")
-        );
-
-        assert_eq!(
-            datetime!(2024-11-14 08:59:19.273124000 +0000),
-            time::OffsetDateTime::from_value("1731574759.273124000 1440").expect("deserializing")
-        );
+    fn date(year: i32, month: u8, day: u8) -> time::Date {
+        time::Date::from_calendar_date(year, month.try_into().unwrap(), day).unwrap()
     }
 
-    /// when encountering a date that cannot fit into a normal time.
-    /// (i.e. after the year 9999) we should truncate it to 9999-12-31
-    #[test]
-    fn deserialize_too_large_datetime() {
-        assert_eq!(
-            datetime!(9999-12-31 23:59:59.0 +0000),
-            time::OffsetDateTime::from_value("6318666835200 1440").expect("deserializing")
-        );
+    fn datetime(
+        date: (i32, u8, u8),
+        time: (u8, u8, u8, u32),
+        offset_seconds: i32,
+    ) -> time::OffsetDateTime {
+        naive(date, time)
+            .assume_offset(time::UtcOffset::from_whole_seconds(offset_seconds).unwrap())
+    }
+
+    fn time(hour: u8, minute: u8, second: u8, nanos: u32) -> time::Time {
+        time::Time::from_hms_nano(hour, minute, second, nanos).unwrap()
+    }
+
+    fn naive(
+        (year, month, day): (i32, u8, u8),
+        (hour, minute, second, nanos): (u8, u8, u8, u32),
+    ) -> time::PrimitiveDateTime {
+        time::PrimitiveDateTime::new(date(year, month, day), time(hour, minute, second, nanos))
     }
 
+    crate::epoch::datetime_tests!(
+        date: date -> time::Date,
+        time: time -> time::Time,
+        naive: naive -> time::PrimitiveDateTime,
+        datetime: datetime -> time::OffsetDateTime,
+    );
+
     #[test]
-    fn deserialize_due_to_dates_being_one_off() {
-        assert_eq!(
-            datetime!(2022-02-25 09:16:01.900368 +0000),
-            time::OffsetDateTime::from_value("1645780561.900368000 1440").expect("deserializing")
-        );
-
-        assert_eq!(
-            datetime!(2022-02-25 09:22:22.957985 +0000),
-            time::OffsetDateTime::from_value("1645780942.957985000 1440").expect("deserializing")
-        );
-
-        assert_eq!(
-            datetime!(2022-02-03 00:00:00.000 +0000),
-            time::OffsetDateTime::from_value("1643846400.000000000 1440").expect("deserializing")
-        );
-
-        assert_eq!(
-            datetime!(2025-01-31 22:59:59.000 +0000),
-            time::OffsetDateTime::from_value("1738364399.000000000 1440").expect("deserializing")
-        );
+    fn bind_null() {
+        let value = BindingValue::from(None::<time::OffsetDateTime>);
+        assert!(matches!(value.kind(), BindingKind::TimestampTz));
+        assert_eq!(value.value(), None);
     }
 }
 
@@ -0,0 +1,91 @@
+use rust_decimal::Decimal;
+
+use crate::{BindingKind, BindingType, BindingValue, Error, FromValue, RowType, SnowflakeType};
+
+/// NUMBER columns, parsed exactly.
+///
+/// Values that do not fit in a [`Decimal`], which holds up to 28 significant digits,
+/// are reported as errors rather than rounded.
+impl FromValue for Decimal {
+    fn from_value(s: &str) -> Result<Self, Error> {
+        Decimal::from_str_exact(s).map_err(|err| Error::Value {
+            given: s.into(),
+            err: format!("does not fit in a Decimal: {err}"),
+        })
+    }
+
+    /// Also checks the value against the column's scale.
+    fn from_column(s: Option<&str>, column: Option<&RowType>) -> Result<Self, Error> {
+        let s = s.ok_or(Error::UnexpectedNull)?;
+        let decimal = Self::from_value(s)?;
+
+        if let Some(&SnowflakeType::Fixed { scale, .. }) = column.map(|col| &col.snowflake_type)
+            && i64::from(decimal.scale()) > i64::from(scale)
+        {
+            return Err(Error::Value {
+                given: s.into(),
+                err: format!("has more decimals than the column's scale of {scale}"),
+            });
+        }
+
+        Ok(decimal)
+    }
+
+    fn accepts(column: &RowType) -> bool {
+        match column.snowflake_type {
+            SnowflakeType::Fixed { scale, .. } => scale <= Decimal::MAX_SCALE as i32,
+            _ => false,
+        }
+    }
+}
+
+impl From<Decimal> for BindingValue {
+    fn from(value: Decimal) -> Self {
+        BindingValue::Decimal(value.to_string())
+    }
+}
+
+impl BindingType for Decimal {
+    const KIND: BindingKind = BindingKind::Fixed;
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn number(precision: u32, scale: i32) -> RowType {
+        serde_json::from_value(serde_json::json!({
+            "name": "AMOUNT",
+            "database": "DB",
+            "schema": "PUBLIC",
+            "table": "T",
+            "byteLength": null,
+            "type": "fixed",
+            "precision": precision,
+            "scale": scale,
+            "nullable": false,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_exact_decimals() {
+        let column = number(38, 10);
+
+        let decimal = Decimal::from_column(Some("12345678.1234567891"), Some(&column)).unwrap();
+        assert_eq!(decimal.to_string(), "12345678.1234567891");
+
+        assert!(Decimal::from_column(Some("1.12345678901"), Some(&column)).is_err());
+        assert!(Decimal::from_value("123456789012345678901234567890").is_err());
+        assert!(!Decimal::accepts(&number(38, 30)));
+    }
+
+    #[test]
+    fn bind_decimals() {
+        let value = BindingValue::from(Decimal::new(-1250, 2));
+
+        assert!(matches!(value.kind(), BindingKind::Fixed));
+        assert_eq!(value.value().as_deref(), Some("-12.50"));
+    }
+}
@@ -0,0 +1,265 @@
+use std::sync::Arc;
+
+use crate::{
+    epoch::parse_epoch_nanos, Error, FromRow, FromRowError, Result, RowType, SnowflakeType,
+};
+
+/// A row whose cells are decoded using the result's column metadata,
+/// for results whose shape is not known at compile time.
+#[derive(Clone, Debug)]
+pub struct DynamicRow {
+    columns: Arc<[RowType]>,
+    values: Vec<Value>,
+}
+
+/// A cell of a [`DynamicRow`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Null,
+    /// NUMBER with a scale of 0.
+    Int(i128),
+    /// NUMBER with a scale, kept as its exact decimal text.
+    Decimal(String),
+    Float(f64),
+    Text(String),
+    Bool(bool),
+    /// Days since 1970-01-01.
+    Date(i64),
+    /// Nanoseconds since midnight.
+    Time(u64),
+    Timestamp(Timestamp),
+    Binary(Vec<u8>),
+    /// VARIANT, OBJECT, ARRAY, VECTOR and GeoJSON columns.
+    Json(serde_json::Value),
+}
+
+/// TIMESTAMP_NTZ, TIMESTAMP_LTZ or TIMESTAMP_TZ value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Timestamp {
+    /// Nanoseconds since 1970-01-01 00:00:00 UTC, or wall clock time for TIMESTAMP_NTZ.
+    pub epoch_nanos: i128,
+    /// Offset from UTC in minutes, only set for TIMESTAMP_TZ.
+    pub offset_minutes: Option<i32>,
+}
+
+/// Looks up a [`DynamicRow`] cell by position or column name.
+pub trait ColumnIndex {
+    fn index_in(&self, columns: &[RowType]) -> Option<usize>;
+}
+
+impl ColumnIndex for usize {
+    fn index_in(&self, columns: &[RowType]) -> Option<usize> {
+        (*self < columns.len()).then_some(*self)
+    }
+}
+
+/// Matched exactly first, then ignoring ASCII case.
+impl ColumnIndex for &str {
+    fn index_in(&self, columns: &[RowType]) -> Option<usize> {
+        columns
+            .iter()
+            .position(|col| col.name == *self)
+            .or_else(|| {
+                columns
+                    .iter()
+                    .position(|col| col.name.eq_ignore_ascii_case(self))
+            })
+    }
+}
+
+impl DynamicRow {
+    pub fn get(&self, index: impl ColumnIndex) -> Option<&Value> {
+        index
+            .index_in(&self.columns)
+            .and_then(|idx| self.values.get(idx))
+    }
+
+    pub fn columns(&self) -> &[RowType] {
+        &self.columns
+    }
+
+    pub fn values(&self) -> &[Value] {
+        &self.values
+    }
+
+    pub fn into_values(self) -> Vec<Value> {
+        self.values
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Columns paired with their values.
+    pub fn iter(&self) -> impl Iterator<Item = (&RowType, &Value)> {
+        self.columns.iter().zip(&self.values)
+    }
+}
+
+impl FromRow for DynamicRow {
+    fn from_row<'de, A>(seq: crate::RowAccess<'_, A>) -> std::result::Result<Self, FromRowError>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let (columns, raw) = seq.into_named()?.into_parts();
+
+        let values = columns
+            .iter()
+            .zip(raw)
+            .map(|(col, value)| {
+                Value::decode(&col.snowflake_type, value.as_deref()).map_err(|err| {
+                    FromRowError::Custom(format!("decoding column `{}`: {err}", col.name))
+                })
+            })
+            .collect::<std::result::Result<_, _>>()?;
+
+        Ok(Self { columns, values })
+    }
+}
+
+impl Value {
+    /// Decodes a cell of a `jsonv2` result.
+    pub fn decode(ty: &SnowflakeType, s: Option<&str>) -> Result<Self> {
+        let Some(s) = s else {
+            return Ok(Self::Null);
+        };
+
+        let value = match ty {
+            SnowflakeType::Fixed { scale: 0, .. } => s
+                .parse::<i128>()
+                .map(Self::Int)
+                .unwrap_or_else(|_| Self::Decimal(s.into())),
+            SnowflakeType::Fixed { .. } => Self::Decimal(s.into()),
+            SnowflakeType::Real => Self::Float(s.parse().map_err(|err| format_err(s, err))?),
+            SnowflakeType::Boolean => Self::Bool(match s {
+                "true" | "1" => true,
+                "false" | "0" => false,
+                _ => return Err(format_err(s, "expected a boolean")),
+            }),
+            SnowflakeType::Date => Self::Date(s.parse().map_err(|err| format_err(s, err))?),
+            SnowflakeType::Time { .. } => {
+                let nanos = parse_epoch_nanos(s)?;
+                Self::Time(u64::try_from(nanos).map_err(|err| format_err(s, err))?)
+            }
+            SnowflakeType::TimestampNtz { .. } | SnowflakeType::TimestampLtz { .. } => {
+                Self::Timestamp(Timestamp {
+                    epoch_nanos: parse_epoch_nanos(s)?,
+                    offset_minutes: None,
+                })
+            }
+            SnowflakeType::TimestampTz { .. } => {
+                let (epoch, offset) = s
+                    .split_once(' ')
+                    .ok_or_else(|| format_err(s, "expected a timezone offset"))?;
+
+                // The offset is sent as minutes + 1440, to keep it positive.
+                let offset = offset.parse::<i32>().map_err(|err| format_err(s, err))? - 1440;
+
+                Self::Timestamp(Timestamp {
+                    epoch_nanos: parse_epoch_nanos(epoch)?,
+                    offset_minutes: Some(offset),
+                })
+            }
+            SnowflakeType::Binary { .. } => Self::Binary(decode_hex(s)?),
+            SnowflakeType::Variant
+            | SnowflakeType::Object
+            | SnowflakeType::Array
+            | SnowflakeType::Vector => {
+                Self::Json(serde_json::from_str(s).map_err(|err| format_err(s, err))?)
+            }
+            // GeoJSON by default, but WKT and other output formats are plain text.
+            SnowflakeType::Geography | SnowflakeType::Geometry => serde_json::from_str(s)
+                .map(Self::Json)
+                .unwrap_or_else(|_| Self::Text(s.into())),
+            SnowflakeType::Text { .. } | SnowflakeType::Unknown(_) => Self::Text(s.into()),
+        };
+
+        Ok(value)
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>> {
+    let digit = |b: u8| {
+        char::from(b)
+            .to_digit(16)
+            .ok_or_else(|| format_err(s, "expected hex digits"))
+    };
+
+    if !s.len().is_multiple_of(2) {
+        return Err(format_err(s, "expected an even number of hex digits"));
+    }
+
+    s.as_bytes()
+        .chunks(2)
+        .map(|pair| Ok((digit(pair[0])? * 16 + digit(pair[1])?) as u8))
+        .collect()
+}
+
+fn format_err(s: &str, err: impl ToString) -> Error {
+    Error::Format {
+        given: s.into(),
+        err: err.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn decode_values() {
+        let decode = |ty, s| Value::decode(&ty, Some(s)).unwrap();
+
+        assert_eq!(
+            decode(
+                SnowflakeType::Fixed {
+                    precision: 38,
+                    scale: 0
+                },
+                "-12"
+            ),
+            Value::Int(-12)
+        );
+        assert_eq!(
+            decode(
+                SnowflakeType::Fixed {
+                    precision: 10,
+                    scale: 2
+                },
+                "1.50"
+            ),
+            Value::Decimal("1.50".into())
+        );
+        assert_eq!(
+            decode(SnowflakeType::Binary { length: None }, "0aff"),
+            Value::Binary(vec![10, 255])
+        );
+        assert_eq!(
+            decode(
+                SnowflakeType::TimestampTz { scale: 9 },
+                "1616173619.000000000 1500"
+            ),
+            Value::Timestamp(Timestamp {
+                epoch_nanos: 1_616_173_619_000_000_000,
+                offset_minutes: Some(60),
+            })
+        );
+        assert_eq!(
+            decode(SnowflakeType::Variant, r#"{"a": 1}"#),
+            Value::Json(serde_json::json!({ "a": 1 }))
+        );
+        assert_eq!(
+            Value::decode(&SnowflakeType::Real, None).unwrap(),
+            Value::Null
+        );
+    }
+}
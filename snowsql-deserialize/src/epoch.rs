@@ -0,0 +1,348 @@
+//! Parsing of dates and timestamps shared by the `time` and `chrono` features
+//! and [`crate::DynamicRow`], so all read Snowflake values the same way.
+
+use crate::Error;
+
+/// Parses `seconds[.fraction]` into nanoseconds, the fraction having at most 9 digits.
+pub(crate) fn parse_epoch_nanos(s: &str) -> Result<i128, Error> {
+    let format_err = |err: &dyn ToString| Error::Format {
+        given: s.into(),
+        err: err.to_string(),
+    };
+
+    let (secs, frac) = s.split_once('.').unwrap_or((s, ""));
+
+    if 9 < frac.len() || !frac.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format_err(&"expected at most 9 fractional digits"));
+    }
+
+    let secs = secs.parse::<i128>().map_err(|err| format_err(&err))?;
+    let frac = format!("{frac:0<9}")
+        .parse::<i128>()
+        .map_err(|err| format_err(&err))?;
+
+    let nanos = secs
+        .checked_abs()
+        .and_then(|secs| secs.checked_mul(1_000_000_000))
+        .and_then(|nanos| nanos.checked_add(frac))
+        .ok_or_else(|| format_err(&"value too large"))?;
+
+    Ok(if s.starts_with('-') { -nanos } else { nanos })
+}
+
+/// 9999-12-31 23:59:59 UTC, later timestamps are truncated to it.
+#[cfg(any(feature = "time", feature = "chrono"))]
+const MAX_EPOCH_SECONDS: i64 = 253_402_300_799;
+
+/// A `DATE` value, the number of days since the epoch (e.g. 18262).
+#[cfg(any(feature = "time", feature = "chrono"))]
+pub(crate) fn parse_days(s: &str) -> Result<i64, Error> {
+    s.parse::<i64>().map_err(|_| Error::Format {
+        given: s.into(),
+        err: "expected a string with the number of days since epoch.".into(),
+    })
+}
+
+/// A timestamp value, split in its parts.
+#[cfg(any(feature = "time", feature = "chrono"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct EpochTimestamp {
+    /// Seconds since the epoch, in UTC.
+    pub secs: i64,
+    /// Nanoseconds added to `secs`, also for values before the epoch.
+    pub nanos: u32,
+    /// Only set for `TIMESTAMP_TZ`.
+    pub offset_minutes: Option<i32>,
+}
+
+/// TIMESTAMP_LTZ, TIMESTAMP_NTZ
+///     Float value (with 9 decimal places) of the number of seconds since the epoch (e.g. 82919.000000000).
+///
+/// TIMESTAMP_TZ
+///     Float value (with 9 decimal places) of the number of seconds since the epoch,
+///     followed by a space and the time zone offset in minutes (e.g. 1616173619000000000 960)
+#[cfg(any(feature = "time", feature = "chrono"))]
+pub(crate) fn parse_timestamp(s: &str) -> Result<EpochTimestamp, Error> {
+    let Some((epoch, offset_part)) = s.split_once(' ') else {
+        return parse_epoch(s, s);
+    };
+
+    let offset_minutes = offset_part.parse::<i32>().map_err(|err| Error::Format {
+        given: s.into(),
+        err: format!("parsing Offset minutes from `{offset_part}`: {err}"),
+    })?;
+
+    // The offset is sent as minutes plus 1440 (24h) to keep it positive,
+    // e.g. 960 is -08:00 and 1440 is UTC.
+
+    Ok(EpochTimestamp {
+        offset_minutes: Some(offset_minutes - 1440),
+        ..parse_epoch(s, epoch)?
+    })
+}
+
+/// TIME
+///     Float value (with 9 decimal places) of the number of seconds since midnight (e.g. 82919.000000000).
+///
+/// Returns the seconds and nanoseconds since midnight.
+#[cfg(any(feature = "time", feature = "chrono"))]
+pub(crate) fn parse_time(s: &str) -> Result<(u32, u32), Error> {
+    let time = parse_epoch(s, s)?;
+
+    match u32::try_from(time.secs) {
+        Ok(secs) if secs < 24 * 60 * 60 => Ok((secs, time.nanos)),
+        _ => Err(Error::Value {
+            given: s.into(),
+            err: "expected the number of seconds since midnight".into(),
+        }),
+    }
+}
+
+#[cfg(any(feature = "time", feature = "chrono"))]
+fn parse_epoch(s: &str, epoch: &str) -> Result<EpochTimestamp, Error> {
+    let nanos = parse_epoch_nanos(epoch).map_err(|err| match err {
+        Error::Format { err, .. } => Error::Format {
+            given: s.into(),
+            err: format!("parsing seconds from `{epoch}`: {err}"),
+        },
+        err => err,
+    })?;
+
+    let secs = nanos.div_euclid(1_000_000_000);
+
+    if i128::from(MAX_EPOCH_SECONDS) < secs {
+        return Ok(EpochTimestamp {
+            secs: MAX_EPOCH_SECONDS,
+            nanos: 0,
+            offset_minutes: None,
+        });
+    }
+
+    Ok(EpochTimestamp {
+        secs: i64::try_from(secs).map_err(|err| Error::Value {
+            given: s.into(),
+            err: err.to_string(),
+        })?,
+        nanos: nanos.rem_euclid(1_000_000_000) as u32,
+        offset_minutes: None,
+    })
+}
+
+/// Tests run against each datetime backend, given functions building its values:
+///
+/// - `date(year, month, day)`
+/// - `time(hour, minute, second, nanosecond)`
+/// - `naive((year, month, day), (hour, minute, second, nanosecond))`
+/// - `datetime((year, month, day), (hour, minute, second, nanosecond), offset_seconds)`
+#[cfg(all(test, any(feature = "time", feature = "chrono")))]
+macro_rules! datetime_tests {
+    (
+        date: $date:ident -> $date_ty:ty,
+        time: $time:ident -> $time_ty:ty,
+        naive: $naive:ident -> $naive_ty:ty,
+        datetime: $datetime:ident -> $datetime_ty:ty $(,)?
+    ) => {
+        type Ymd = (i32, u8, u8);
+        type Hmsn = (u8, u8, u8, u32);
+
+        fn assert_date((y, m, d): Ymd, s: &str) {
+            assert_eq!($date(y, m, d), <$date_ty>::from_value(s).expect(s));
+        }
+
+        fn assert_time((h, m, sec, nanos): Hmsn, s: &str) {
+            assert_eq!($time(h, m, sec, nanos), <$time_ty>::from_value(s).expect(s));
+        }
+
+        fn assert_naive(date: Ymd, time: Hmsn, s: &str) {
+            assert_eq!($naive(date, time), <$naive_ty>::from_value(s).expect(s));
+        }
+
+        fn assert_datetime(date: Ymd, time: Hmsn, offset_seconds: i32, s: &str) {
+            let expected = $datetime(date, time, offset_seconds);
+            let actual = <$datetime_ty>::from_value(s).expect(s);
+
+            assert_eq!(expected, actual, "{s}");
+            // equality only compares the instants, the binding also has the offset
+            assert_eq!(
+                BindingValue::from(expected).to_string(),
+                BindingValue::from(actual).to_string(),
+                "{s}"
+            );
+        }
+
+        #[test]
+        fn deserialize_date() {
+            assert_date((1970, 1, 1), "0");
+            assert_date((2024, 2, 6), "19759");
+            assert_date((1969, 12, 31), "-1");
+            assert!(<$date_ty>::from_value("1.5").is_err());
+        }
+
+        #[test]
+        fn deserialize_time() {
+            assert_time((0, 0, 0, 0), "0.000000000");
+            assert_time((23, 1, 59, 0), "82919.000000000");
+            assert_time((12, 0, 0, 500_000_000), "43200.5");
+            assert_time((23, 59, 59, 999_999_999), "86399.999999999");
+
+            for s in [
+                "86400.000000000",
+                "-1.000000000",
+                "82919.000000000 1440",
+                "abc",
+            ] {
+                assert!(<$time_ty>::from_value(s).is_err(), "{s}");
+            }
+        }
+
+        #[test]
+        fn deserialize_timestamp_ntz() {
+            assert_naive(
+                (2024, 10, 17),
+                (12, 3, 22, 422_528_000),
+                "1729166602.422528000",
+            );
+            assert_naive((1970, 1, 1), (0, 0, 1, 500_000_000), "1.5");
+            assert_naive((1969, 12, 31), (23, 59, 58, 500_000_000), "-1.500000000");
+            assert_naive((1930, 11, 18), (0, 28, 30, 0), "-1234567890.000000000");
+        }
+
+        #[test]
+        fn deserialize_date_time() {
+            assert_datetime(
+                (2024, 10, 17),
+                (12, 3, 22, 422_528_000),
+                0,
+                "1729166602.422528000",
+            );
+            assert_datetime(
+                (2024, 10, 18),
+                (10, 54, 22, 912_451_000),
+                0,
+                "1729248862.912451000",
+            );
+            assert_datetime((2024, 2, 6), (14, 5, 30, 0), 0, "1707228330 1440");
+            assert_datetime(
+                (2024, 11, 14),
+                (8, 59, 19, 273_124_000),
+                0,
+                "1731574759.273124000 1440",
+            );
+        }
+
+        #[test]
+        fn deserialize_due_to_dates_being_one_off() {
+            assert_datetime(
+                (2022, 2, 25),
+                (9, 16, 1, 900_368_000),
+                0,
+                "1645780561.900368000 1440",
+            );
+            assert_datetime(
+                (2022, 2, 25),
+                (9, 22, 22, 957_985_000),
+                0,
+                "1645780942.957985000 1440",
+            );
+            assert_datetime((2022, 2, 3), (0, 0, 0, 0), 0, "1643846400.000000000 1440");
+            assert_datetime(
+                (2025, 1, 31),
+                (22, 59, 59, 0),
+                0,
+                "1738364399.000000000 1440",
+            );
+        }
+
+        #[test]
+        fn deserialize_timestamp_tz_offsets() {
+            assert_datetime(
+                (2021, 3, 19),
+                (9, 6, 59, 0),
+                -8 * 3600,
+                "1616173619.000000000 960",
+            );
+            assert_datetime(
+                (2024, 2, 6),
+                (19, 35, 30, 0),
+                5 * 3600 + 1800,
+                "1707228330.000000000 1770",
+            );
+            assert_datetime(
+                (1969, 12, 31),
+                (15, 59, 59, 0),
+                -8 * 3600,
+                "-1.000000000 960",
+            );
+            assert_datetime(
+                (1930, 11, 17),
+                (23, 28, 30, 250_000_000),
+                -3600,
+                "-1234567889.750000000 1380",
+            );
+        }
+
+        /// when encountering a date that cannot fit into a normal time.
+        /// (i.e. after the year 9999) we should truncate it to 9999-12-31
+        #[test]
+        fn deserialize_too_large_datetime() {
+            assert_datetime((9999, 12, 31), (23, 59, 59, 0), 0, "6318666835200 1440");
+        }
+
+        #[test]
+        fn reject_invalid_datetime() {
+            for s in ["", "abc", "1.abc", "1 abc", "1.5 1440 3", "1.0000000001"] {
+                assert!(<$datetime_ty>::from_value(s).is_err(), "{s}");
+            }
+        }
+
+        #[test]
+        fn bind_dates_and_times() {
+            let value = BindingValue::from($date(2024, 2, 6));
+            assert!(matches!(value.kind(), BindingKind::Date));
+            assert_eq!(value.to_string(), "2024-02-06");
+
+            let value = BindingValue::from($time(8, 5, 3, 12_300_000));
+            assert!(matches!(value.kind(), BindingKind::Time));
+            assert_eq!(value.to_string(), "08:05:03.012300000");
+
+            let value = BindingValue::from($naive((2024, 2, 6), (14, 5, 30, 500_000_000)));
+            assert!(matches!(value.kind(), BindingKind::TimestampNtz));
+            assert_eq!(value.to_string(), "2024-02-06 14:05:30.500000000");
+
+            let value =
+                BindingValue::from($datetime((2024, 2, 6), (14, 5, 30, 0), -(3 * 3600 + 1800)));
+            assert!(matches!(value.kind(), BindingKind::TimestampTz));
+            assert_eq!(value.to_string(), "2024-02-06 14:05:30.000000000 -03:30");
+
+            let value = BindingValue::from(TimestampLtz($datetime(
+                (2024, 2, 6),
+                (14, 5, 30, 500_000_000),
+                3600,
+            )));
+            assert!(matches!(value.kind(), BindingKind::TimestampLtz));
+            assert_eq!(value.to_string(), "2024-02-06 14:05:30.500000000 +01:00");
+        }
+    };
+}
+
+#[cfg(all(test, any(feature = "time", feature = "chrono")))]
+pub(crate) use datetime_tests;
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn epoch_nanos() {
+        assert_eq!(
+            parse_epoch_nanos("82919.000000000").unwrap(),
+            82_919_000_000_000
+        );
+        assert_eq!(parse_epoch_nanos("1.5").unwrap(), 1_500_000_000);
+        assert_eq!(parse_epoch_nanos("-1.500000000").unwrap(), -1_500_000_000);
+        assert_eq!(parse_epoch_nanos("-0.000000001").unwrap(), -1);
+        assert_eq!(parse_epoch_nanos("17").unwrap(), 17_000_000_000);
+        assert!(parse_epoch_nanos("1.0000000001").is_err());
+    }
+}
@@ -21,12 +21,21 @@ pub enum FromRowError {
     #[error("Field `{field}` could not be parsed as &str: {err}")]
     NonStrField { field: &'static str, err: String },
 
+    #[error("Column `{column}` could not be parsed as &str: {err}")]
+    NonStrColumn { column: String, err: String },
+
     #[error("invalid row length, expected column {idx} for field `{field}`")]
     MissingField { idx: usize, field: &'static str },
 
     #[error("deserializing field `{field}`: {err}")]
     DeserializingField { field: &'static str, err: Error },
 
+    #[error("column `{column}` not found in the result")]
+    MissingColumn { column: &'static str },
+
+    #[error("reading columns by name needs the result's row type metadata")]
+    MissingColumnMetadata,
+
     #[error("{0}")]
     Custom(String),
 }
@@ -0,0 +1,103 @@
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    BindingKind, BindingType, BindingValue, Error, FromValue, Result, RowType, SnowflakeType,
+};
+
+/// A VARIANT, OBJECT or ARRAY column deserialized with serde,
+/// or a value bound as JSON text.
+///
+/// Bound values need `PARSE_JSON(?)` in the statement to be stored as a VARIANT.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Json<T>(pub T);
+
+/// Deserializes a JSON column, a null column being deserialized from `null`.
+///
+/// Used by `#[snowsql(json)]`.
+pub fn from_json<T>(s: Option<&str>) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    match s {
+        Some(s) => serde_json::from_str(s).map_err(|err| Error::Format {
+            given: s.into(),
+            err: err.to_string(),
+        }),
+        None => serde_json::from_str("null").map_err(|_| Error::UnexpectedNull),
+    }
+}
+
+/// Semi-structured columns, and text columns which may hold JSON.
+pub fn accepts_json(column: &RowType) -> bool {
+    matches!(
+        column.snowflake_type,
+        SnowflakeType::Variant
+            | SnowflakeType::Object
+            | SnowflakeType::Array
+            | SnowflakeType::Vector
+            | SnowflakeType::Geography
+            | SnowflakeType::Geometry
+            | SnowflakeType::Text { .. }
+    )
+}
+
+impl<T> FromValue for Json<T>
+where
+    T: DeserializeOwned,
+{
+    fn from_value(s: &str) -> Result<Self> {
+        from_json(Some(s)).map(Json)
+    }
+
+    fn accepts(column: &RowType) -> bool {
+        accepts_json(column)
+    }
+}
+
+impl FromValue for serde_json::Value {
+    const NULLABLE: bool = true;
+
+    fn from_value(s: &str) -> Result<Self> {
+        from_json(Some(s))
+    }
+
+    /// A null column is [`serde_json::Value::Null`].
+    fn from_optional_value(s: Option<&str>) -> Result<Self> {
+        from_json(s)
+    }
+
+    fn accepts(column: &RowType) -> bool {
+        accepts_json(column)
+    }
+}
+
+/// Fails when `T` does not serialize, for example a map with non-string keys.
+impl<T> TryFrom<Json<T>> for BindingValue
+where
+    T: Serialize,
+{
+    type Error = serde_json::Error;
+
+    fn try_from(value: Json<T>) -> std::result::Result<Self, Self::Error> {
+        serde_json::to_string(&value.0).map(json_binding)
+    }
+}
+
+impl From<serde_json::Value> for BindingValue {
+    fn from(value: serde_json::Value) -> Self {
+        json_binding(value.to_string())
+    }
+}
+
+impl BindingType for serde_json::Value {
+    const KIND: BindingKind = BindingKind::Text;
+}
+
+/// `null` binds as a SQL null, so `PARSE_JSON(?)` gives NULL rather than a JSON null.
+fn json_binding(json: String) -> BindingValue {
+    if json == "null" {
+        BindingValue::Null(BindingKind::Text)
+    } else {
+        BindingValue::Json(json)
+    }
+}
@@ -2,19 +2,36 @@ use std::str::FromStr;
 
 pub mod bindings;
 
+mod columns;
+mod dynamic;
+mod epoch;
 mod error;
+mod json;
 mod raw_row;
 mod row;
+mod schema;
+mod snowflake_type;
 
 pub use {
+    columns::RowType,
+    dynamic::{ColumnIndex, DynamicRow, Timestamp, Value},
     error::{Error, FromRowError},
+    json::{accepts_json, from_json, Json},
     raw_row::RawRow,
-    row::{FromRow, Row, RowAccess},
+    row::{FromRow, NamedRowAccess, Row, RowAccess, RowsSeed},
+    schema::{ColumnSchema, Schema, SchemaIssue, SchemaMismatch},
+    snowflake_type::SnowflakeType,
 };
 
 #[cfg(feature = "time")]
 mod datetime;
 
+#[cfg(feature = "chrono")]
+mod chrono_datetime;
+
+#[cfg(feature = "rust_decimal")]
+mod decimal;
+
 pub use bindings::*;
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -28,17 +45,34 @@ pub trait FromValue
 where
     Self: Sized,
 {
+    /// Whether null is a valid value, used to validate a [`Schema`].
+    const NULLABLE: bool = false;
+
     fn from_value(s: &str) -> Result<Self>;
 
     fn from_optional_value(s: Option<&str>) -> Result<Self> {
         s.ok_or(Error::UnexpectedNull).and_then(Self::from_value)
     }
+
+    /// Like [`Self::from_optional_value`], also given the column's metadata when the result provides it.
+    fn from_column(s: Option<&str>, _column: Option<&RowType>) -> Result<Self> {
+        Self::from_optional_value(s)
+    }
+
+    /// Whether values of `column` can be parsed, used to validate a [`Schema`].
+    ///
+    /// Accepts any column unless overridden.
+    fn accepts(_column: &RowType) -> bool {
+        true
+    }
 }
 
 impl<T> FromValue for Option<T>
 where
     T: FromValue,
 {
+    const NULLABLE: bool = true;
+
     fn from_value(s: &str) -> Result<Self> {
         T::from_value(s).map(Some)
     }
@@ -46,9 +80,17 @@ where
     fn from_optional_value(s: Option<&str>) -> Result<Self> {
         s.map(T::from_value).transpose()
     }
+
+    fn from_column(s: Option<&str>, column: Option<&RowType>) -> Result<Self> {
+        s.map(|s| T::from_column(Some(s), column)).transpose()
+    }
+
+    fn accepts(column: &RowType) -> bool {
+        T::accepts(column)
+    }
 }
 macro_rules! impl_from_value {
-    ($ty: ty) => {
+    ($ty: ty, $accepts: expr) => {
         impl FromValue for $ty {
             fn from_value(s: &str) -> Result<Self> {
                 <$ty>::from_str(s).map_err(|err| Error::Format {
@@ -56,23 +98,61 @@ macro_rules! impl_from_value {
                     err: err.to_string(),
                 })
             }
+
+            fn accepts(column: &RowType) -> bool {
+                $accepts(column)
+            }
         }
     };
 }
 
-impl_from_value!(bool);
-impl_from_value!(usize);
-impl_from_value!(isize);
-impl_from_value!(u8);
-impl_from_value!(u16);
-impl_from_value!(u32);
-impl_from_value!(u64);
-impl_from_value!(u128);
-impl_from_value!(i16);
-impl_from_value!(i32);
-impl_from_value!(i64);
-impl_from_value!(i128);
-impl_from_value!(f32);
-impl_from_value!(f64);
-impl_from_value!(String);
-impl_from_value!(uuid::Uuid);
+fn is_boolean(column: &RowType) -> bool {
+    column.snowflake_type == SnowflakeType::Boolean
+}
+
+fn is_integer(column: &RowType) -> bool {
+    matches!(column.snowflake_type, SnowflakeType::Fixed { scale: 0, .. })
+}
+
+fn is_number(column: &RowType) -> bool {
+    matches!(
+        column.snowflake_type,
+        SnowflakeType::Fixed { .. } | SnowflakeType::Real
+    )
+}
+
+fn is_text(column: &RowType) -> bool {
+    matches!(column.snowflake_type, SnowflakeType::Text { .. })
+}
+
+/// Text and semi-structured columns, but not numbers, booleans or dates.
+fn is_stringly(column: &RowType) -> bool {
+    matches!(
+        column.snowflake_type,
+        SnowflakeType::Text { .. }
+            | SnowflakeType::Binary { .. }
+            | SnowflakeType::Variant
+            | SnowflakeType::Object
+            | SnowflakeType::Array
+            | SnowflakeType::Geography
+            | SnowflakeType::Geometry
+            | SnowflakeType::Vector
+    )
+}
+
+impl_from_value!(bool, is_boolean);
+impl_from_value!(usize, is_integer);
+impl_from_value!(isize, is_integer);
+impl_from_value!(u8, is_integer);
+impl_from_value!(u16, is_integer);
+impl_from_value!(u32, is_integer);
+impl_from_value!(u64, is_integer);
+impl_from_value!(u128, is_integer);
+impl_from_value!(i16, is_integer);
+impl_from_value!(i32, is_integer);
+impl_from_value!(i64, is_integer);
+impl_from_value!(i128, is_integer);
+impl_from_value!(f32, is_number);
+impl_from_value!(f64, is_number);
+impl_from_value!(String, is_stringly);
+impl_from_value!(uuid::Uuid, is_text);
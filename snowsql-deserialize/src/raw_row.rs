@@ -4,7 +4,7 @@ use crate::{FromRow, FromRowError, RowAccess};
 pub struct RawRow(pub Vec<Option<String>>);
 
 impl FromRow for RawRow {
-    fn from_row<'de, A>(mut seq: RowAccess<A>) -> Result<Self, FromRowError>
+    fn from_row<'de, A>(mut seq: RowAccess<'_, A>) -> Result<Self, FromRowError>
     where
         A: serde::de::SeqAccess<'de>,
     {
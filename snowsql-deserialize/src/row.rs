@@ -1,72 +1,296 @@
-use std::{borrow::Cow, marker::PhantomData};
+use std::{borrow::Cow, marker::PhantomData, sync::Arc};
 
-use crate::{FromRowError, FromValue};
+use serde::de::DeserializeSeed;
+
+use crate::{FromRowError, FromValue, RowType};
 
 pub trait FromRow
 where
     Self: Sized,
 {
-    fn from_row<'de, A>(seq: RowAccess<A>) -> Result<Self, FromRowError>
+    fn from_row<'de, A>(seq: RowAccess<'_, A>) -> Result<Self, FromRowError>
     where
         A: serde::de::SeqAccess<'de>;
 }
 #[derive(Debug)]
 pub struct Row<T>(pub T);
 
-pub struct RowAccess<A> {
+pub struct RowAccess<'c, A> {
     idx: usize,
     seq: A,
+    /// Column metadata of the result, if known, shared by all its rows.
+    columns: Option<&'c Arc<[RowType]>>,
 }
-impl<'de, A> RowAccess<A>
+impl<'c, 'de, A> RowAccess<'c, A>
 where
     A: serde::de::SeqAccess<'de>,
 {
     pub fn new(seq: A) -> Self {
-        Self { idx: 0, seq }
+        Self {
+            idx: 0,
+            seq,
+            columns: None,
+        }
+    }
+
+    /// Gives the row its result's column metadata,
+    /// needed by [`Self::into_named`] and types reading the column's type.
+    pub fn with_columns(mut self, columns: Option<&'c Arc<[RowType]>>) -> Self {
+        self.columns = columns;
+        self
     }
 
     pub fn next<T>(&mut self, field: &'static str) -> Result<T, FromRowError>
     where
         T: FromValue,
     {
+        self.next_in(field, T::from_column)
+    }
+
+    /// Parses the next column with `f` instead of [`FromValue`].
+    pub fn next_with<T, F>(&mut self, field: &'static str, f: F) -> Result<T, FromRowError>
+    where
+        F: FnOnce(Option<&str>) -> crate::Result<T>,
+    {
+        self.next_in(field, |value, _| f(value))
+    }
+
+    /// Like [`Self::next`], but a null or missing column gives `T::default()`.
+    pub fn next_or_default<T>(&mut self, field: &'static str) -> Result<T, FromRowError>
+    where
+        T: FromValue + Default,
+    {
+        self.next_or_default_in(field, T::from_column)
+    }
+
+    /// Like [`Self::next_with`], but a null or missing column gives `T::default()`.
+    pub fn next_or_default_with<T, F>(
+        &mut self,
+        field: &'static str,
+        f: F,
+    ) -> Result<T, FromRowError>
+    where
+        T: Default,
+        F: FnOnce(Option<&str>) -> crate::Result<T>,
+    {
+        self.next_or_default_in(field, |value, _| f(value))
+    }
+
+    fn next_in<T, F>(&mut self, field: &'static str, f: F) -> Result<T, FromRowError>
+    where
+        F: FnOnce(Option<&str>, Option<&RowType>) -> crate::Result<T>,
+    {
+        let idx = self.idx;
+
         let res = self
-            .seq
-            .next_element::<Option<Cow<'de, str>>>()
-            .map_err(|err| FromRowError::NonStrField {
-                field,
-                err: err.to_string(),
-            })?
-            .ok_or(FromRowError::MissingField {
-                idx: self.idx,
-                field,
-            })?;
+            .next_value(field)?
+            .ok_or(FromRowError::MissingField { idx, field })?;
 
         self.idx += 1;
 
-        T::from_optional_value(res.as_deref())
+        f(res.as_deref(), self.column(idx))
             .map_err(|err| FromRowError::DeserializingField { field, err })
     }
 
+    fn next_or_default_in<T, F>(&mut self, field: &'static str, f: F) -> Result<T, FromRowError>
+    where
+        T: Default,
+        F: FnOnce(Option<&str>, Option<&RowType>) -> crate::Result<T>,
+    {
+        let idx = self.idx;
+
+        let Some(res) = self.next_value(field)? else {
+            return Ok(T::default());
+        };
+
+        self.idx += 1;
+
+        match res.as_deref() {
+            None => Ok(T::default()),
+            value => f(value, self.column(idx))
+                .map_err(|err| FromRowError::DeserializingField { field, err }),
+        }
+    }
+
+    fn column(&self, idx: usize) -> Option<&RowType> {
+        self.columns.and_then(|columns| columns.get(idx))
+    }
+
+    /// The next column, or `None` when the row has no more columns.
+    fn next_value(
+        &mut self,
+        field: &'static str,
+    ) -> Result<Option<Option<Cow<'de, str>>>, FromRowError> {
+        self.seq
+            .next_element::<Option<Cow<'de, str>>>()
+            .map_err(|err| FromRowError::NonStrField {
+                field,
+                err: err.to_string(),
+            })
+    }
+
     pub fn size_hint(&self) -> Option<usize> {
         self.seq.size_hint()
     }
+
+    /// Reads the whole row to look up columns by name,
+    /// using the metadata set with [`Self::with_columns`].
+    pub fn into_named(mut self) -> Result<NamedRowAccess<'c, 'de>, FromRowError> {
+        let columns = self.columns.ok_or(FromRowError::MissingColumnMetadata)?;
+
+        let mut values = Vec::with_capacity(columns.len());
+
+        loop {
+            let value = self
+                .seq
+                .next_element::<Option<Cow<'de, str>>>()
+                .map_err(|err| FromRowError::NonStrColumn {
+                    column: columns
+                        .get(values.len())
+                        .map_or_else(|| values.len().to_string(), |col| col.name.clone()),
+                    err: err.to_string(),
+                })?;
+
+            match value {
+                Some(value) => values.push(value),
+                None => break,
+            }
+        }
+
+        Ok(NamedRowAccess { columns, values })
+    }
+}
+
+/// A row whose columns are looked up by name, created with [`RowAccess::into_named`].
+///
+/// Names are matched exactly first, then ignoring ASCII case,
+/// since Snowflake upper cases unquoted identifiers.
+pub struct NamedRowAccess<'c, 'de> {
+    columns: &'c Arc<[RowType]>,
+    values: Vec<Option<Cow<'de, str>>>,
+}
+
+impl<'de> NamedRowAccess<'_, 'de> {
+    pub fn get<T>(&self, column: &'static str) -> Result<T, FromRowError>
+    where
+        T: FromValue,
+    {
+        self.get_in(column, T::from_column)
+    }
+
+    pub fn get_with<T, F>(&self, column: &'static str, f: F) -> Result<T, FromRowError>
+    where
+        F: FnOnce(Option<&str>) -> crate::Result<T>,
+    {
+        self.get_in(column, |value, _| f(value))
+    }
+
+    /// Like [`Self::get`], but a null or missing column gives `T::default()`.
+    pub fn get_or_default<T>(&self, column: &'static str) -> Result<T, FromRowError>
+    where
+        T: FromValue + Default,
+    {
+        self.get_or_default_in(column, T::from_column)
+    }
+
+    /// Like [`Self::get_with`], but a null or missing column gives `T::default()`.
+    pub fn get_or_default_with<T, F>(&self, column: &'static str, f: F) -> Result<T, FromRowError>
+    where
+        T: Default,
+        F: FnOnce(Option<&str>) -> crate::Result<T>,
+    {
+        self.get_or_default_in(column, |value, _| f(value))
+    }
+
+    fn get_in<T, F>(&self, column: &'static str, f: F) -> Result<T, FromRowError>
+    where
+        F: FnOnce(Option<&str>, Option<&RowType>) -> crate::Result<T>,
+    {
+        let (value, row_type) = self
+            .value(column)
+            .ok_or(FromRowError::MissingColumn { column })?;
+
+        f(value, Some(row_type))
+            .map_err(|err| FromRowError::DeserializingField { field: column, err })
+    }
+
+    fn get_or_default_in<T, F>(&self, column: &'static str, f: F) -> Result<T, FromRowError>
+    where
+        T: Default,
+        F: FnOnce(Option<&str>, Option<&RowType>) -> crate::Result<T>,
+    {
+        match self.value(column) {
+            Some((value @ Some(_), row_type)) => f(value, Some(row_type))
+                .map_err(|err| FromRowError::DeserializingField { field: column, err }),
+            _ => Ok(T::default()),
+        }
+    }
+
+    pub(crate) fn into_parts(self) -> (Arc<[RowType]>, Vec<Option<Cow<'de, str>>>) {
+        (self.columns.clone(), self.values)
+    }
+
+    /// The value and metadata of `column`, or `None` when the result has no such column.
+    fn value(&self, column: &str) -> Option<(Option<&str>, &RowType)> {
+        let idx = self
+            .columns
+            .iter()
+            .position(|col| col.name == column)
+            .or_else(|| {
+                self.columns
+                    .iter()
+                    .position(|col| col.name.eq_ignore_ascii_case(column))
+            })?;
+
+        let value = self.values.get(idx)?;
+
+        Some((value.as_deref(), &self.columns[idx]))
+    }
 }
 
 impl<'de, T> serde::Deserialize<'de> for Row<T>
 where
     T: FromRow,
 {
+    /// Deserializes the row without column metadata, see [`RowsSeed`] to provide it.
     fn deserialize<D>(des: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        des.deserialize_seq(Visitor(PhantomData))
+        RowSeed::<T>::new(None).deserialize(des)
+    }
+}
+
+/// Deserializes a [`Row`] given its result's column metadata.
+struct RowSeed<'c, T> {
+    columns: Option<&'c Arc<[RowType]>>,
+    _marker: PhantomData<T>,
+}
+
+impl<'c, T> RowSeed<'c, T> {
+    fn new(columns: Option<&'c Arc<[RowType]>>) -> Self {
+        Self {
+            columns,
+            _marker: PhantomData,
+        }
     }
 }
 
-struct Visitor<T>(PhantomData<T>);
+impl<'de, T> DeserializeSeed<'de> for RowSeed<'_, T>
+where
+    T: FromRow,
+{
+    type Value = Row<T>;
+
+    fn deserialize<D>(self, des: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        des.deserialize_seq(self)
+    }
+}
 
-impl<'de, T> serde::de::Visitor<'de> for Visitor<T>
+impl<'de, T> serde::de::Visitor<'de> for RowSeed<'_, T>
 where
     T: FromRow,
 {
@@ -80,8 +304,62 @@ where
     where
         A: serde::de::SeqAccess<'de>,
     {
-        T::from_row(RowAccess::new(seq))
+        T::from_row(RowAccess::new(seq).with_columns(self.columns))
             .map_err(|err| serde::de::Error::custom(err.to_string()))
             .map(Row)
     }
 }
+
+/// Deserializes the `data` rows of a result given its column metadata,
+/// which [`FromRow`] implementations reading columns by name or type rely on.
+pub struct RowsSeed<'c, T> {
+    columns: Option<&'c Arc<[RowType]>>,
+    _marker: PhantomData<T>,
+}
+
+impl<'c, T> RowsSeed<'c, T> {
+    pub fn new(columns: Option<&'c Arc<[RowType]>>) -> Self {
+        Self {
+            columns,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, T> DeserializeSeed<'de> for RowsSeed<'_, T>
+where
+    T: FromRow,
+{
+    type Value = Vec<Row<T>>;
+
+    fn deserialize<D>(self, des: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        des.deserialize_seq(self)
+    }
+}
+
+impl<'de, T> serde::de::Visitor<'de> for RowsSeed<'_, T>
+where
+    T: FromRow,
+{
+    type Value = Vec<Row<T>>;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a list of snowflake rows")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let mut rows = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+
+        while let Some(row) = seq.next_element_seed(RowSeed::new(self.columns))? {
+            rows.push(row);
+        }
+
+        Ok(rows)
+    }
+}
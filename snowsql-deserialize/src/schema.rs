@@ -0,0 +1,166 @@
+use std::fmt;
+
+use crate::RowType;
+
+/// Columns a [`FromRow`](crate::FromRow) type expects, checked with [`Schema::validate`].
+#[derive(Clone, Copy, Debug)]
+pub struct Schema {
+    pub columns: &'static [ColumnSchema],
+    /// Columns are looked up by name rather than position, extra columns are allowed.
+    pub by_name: bool,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ColumnSchema {
+    pub name: &'static str,
+    /// Whether the field can hold null, see [`FromValue::NULLABLE`](crate::FromValue::NULLABLE).
+    pub nullable: bool,
+    /// Whether the field can be parsed from the column, see [`FromValue::accepts`](crate::FromValue::accepts).
+    pub accepts: fn(&RowType) -> bool,
+}
+
+impl Schema {
+    /// Expects nothing, every result is valid.
+    pub const ANY: Self = Self {
+        columns: &[],
+        by_name: true,
+    };
+
+    pub fn validate(&self, columns: &[RowType]) -> Result<(), SchemaMismatch> {
+        let mut issues = Vec::new();
+
+        if !self.by_name && self.columns.len() != columns.len() {
+            issues.push(SchemaIssue::ColumnCount {
+                expected: self.columns.len(),
+                actual: columns.len(),
+            });
+        }
+
+        for (idx, expected) in self.columns.iter().enumerate() {
+            let actual = if self.by_name {
+                columns
+                    .iter()
+                    .find(|col| col.name == expected.name)
+                    .or_else(|| {
+                        columns
+                            .iter()
+                            .find(|col| col.name.eq_ignore_ascii_case(expected.name))
+                    })
+            } else {
+                columns.get(idx)
+            };
+
+            let Some(actual) = actual else {
+                issues.push(SchemaIssue::MissingColumn {
+                    column: expected.name,
+                });
+                continue;
+            };
+
+            if !actual.name.eq_ignore_ascii_case(expected.name) {
+                issues.push(SchemaIssue::Name {
+                    idx,
+                    expected: expected.name,
+                    actual: actual.name.clone(),
+                });
+            }
+
+            if !(expected.accepts)(actual) {
+                issues.push(SchemaIssue::Type {
+                    column: expected.name,
+                    data_type: actual.snowflake_type.to_string(),
+                });
+            }
+
+            if actual.nullable && !expected.nullable {
+                issues.push(SchemaIssue::Nullable {
+                    column: expected.name,
+                });
+            }
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(SchemaMismatch { issues })
+        }
+    }
+}
+
+/// Differences between a [`Schema`] and the columns of a result.
+#[derive(Debug, Clone)]
+pub struct SchemaMismatch {
+    pub issues: Vec<SchemaIssue>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaIssue {
+    ColumnCount {
+        expected: usize,
+        actual: usize,
+    },
+    MissingColumn {
+        column: &'static str,
+    },
+    /// The column at `idx` is named differently, only checked for positional schemas.
+    Name {
+        idx: usize,
+        expected: &'static str,
+        actual: String,
+    },
+    Type {
+        column: &'static str,
+        data_type: String,
+    },
+    /// The column is nullable but the field cannot hold null.
+    Nullable {
+        column: &'static str,
+    },
+}
+
+impl fmt::Display for SchemaIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ColumnCount { expected, actual } => {
+                write!(f, "expected {expected} columns, got {actual}")
+            }
+            Self::MissingColumn { column } => write!(f, "column `{column}` is missing"),
+            Self::Name {
+                idx,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "expected column {idx} to be `{expected}`, got `{actual}`"
+            ),
+            Self::Type { column, data_type } => {
+                write!(
+                    f,
+                    "column `{column}` of type {data_type} does not match its field"
+                )
+            }
+            Self::Nullable { column } => {
+                write!(
+                    f,
+                    "column `{column}` is nullable but its field is not an Option"
+                )
+            }
+        }
+    }
+}
+
+impl fmt::Display for SchemaMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, issue) in self.issues.iter().enumerate() {
+            if 0 < i {
+                f.write_str("; ")?;
+            }
+
+            write!(f, "{issue}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for SchemaMismatch {}
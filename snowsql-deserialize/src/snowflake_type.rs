@@ -0,0 +1,107 @@
+use std::fmt;
+
+/// Type of a result column, parsed from its `rowType` entry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SnowflakeType {
+    /// NUMBER and its aliases, INT being `Fixed { precision: 38, scale: 0 }`.
+    Fixed {
+        precision: u32,
+        scale: i32,
+    },
+    Real,
+    Text {
+        length: Option<usize>,
+    },
+    Binary {
+        length: Option<usize>,
+    },
+    Boolean,
+    Date,
+    Time {
+        scale: i32,
+    },
+    TimestampNtz {
+        scale: i32,
+    },
+    TimestampLtz {
+        scale: i32,
+    },
+    TimestampTz {
+        scale: i32,
+    },
+    Variant,
+    Object,
+    Array,
+    Geography,
+    Geometry,
+    Vector,
+    Unknown(String),
+}
+
+impl SnowflakeType {
+    /// Parses the `type` of a `rowType` entry, along with its precision, scale and length.
+    pub fn parse(
+        data_type: &str,
+        precision: Option<u32>,
+        scale: Option<i32>,
+        length: Option<usize>,
+    ) -> Self {
+        let scale = || scale.unwrap_or_default();
+
+        match data_type.to_ascii_lowercase().as_str() {
+            "fixed" | "number" => Self::Fixed {
+                precision: precision.unwrap_or(38),
+                scale: scale(),
+            },
+            "real" | "float" => Self::Real,
+            "text" | "varchar" => Self::Text { length },
+            "binary" => Self::Binary { length },
+            "boolean" => Self::Boolean,
+            "date" => Self::Date,
+            "time" => Self::Time { scale: scale() },
+            "timestamp_ntz" => Self::TimestampNtz { scale: scale() },
+            "timestamp_ltz" => Self::TimestampLtz { scale: scale() },
+            "timestamp_tz" => Self::TimestampTz { scale: scale() },
+            "variant" => Self::Variant,
+            "object" => Self::Object,
+            "array" => Self::Array,
+            "geography" => Self::Geography,
+            "geometry" => Self::Geometry,
+            "vector" => Self::Vector,
+            _ => Self::Unknown(data_type.into()),
+        }
+    }
+
+    pub fn is_timestamp(&self) -> bool {
+        matches!(
+            self,
+            Self::TimestampNtz { .. } | Self::TimestampLtz { .. } | Self::TimestampTz { .. }
+        )
+    }
+}
+
+impl fmt::Display for SnowflakeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fixed { precision, scale } => write!(f, "NUMBER({precision},{scale})"),
+            Self::Real => f.write_str("REAL"),
+            Self::Text { length: Some(len) } => write!(f, "TEXT({len})"),
+            Self::Text { length: None } => f.write_str("TEXT"),
+            Self::Binary { length: Some(len) } => write!(f, "BINARY({len})"),
+            Self::Binary { length: None } => f.write_str("BINARY"),
+            Self::Boolean => f.write_str("BOOLEAN"),
+            Self::Date => f.write_str("DATE"),
+            Self::Time { scale } => write!(f, "TIME({scale})"),
+            Self::TimestampNtz { scale } => write!(f, "TIMESTAMP_NTZ({scale})"),
+            Self::TimestampLtz { scale } => write!(f, "TIMESTAMP_LTZ({scale})"),
+            Self::TimestampTz { scale } => write!(f, "TIMESTAMP_TZ({scale})"),
+            Self::Variant => f.write_str("VARIANT"),
+            Self::Object => f.write_str("OBJECT"),
+            Self::Array => f.write_str("ARRAY"),
+            Self::Geography => f.write_str("GEOGRAPHY"),
+            Self::Geometry => f.write_str("GEOMETRY"),
+            Self::Vector => f.write_str("VECTOR"),
+            Self::Unknown(data_type) => f.write_str(data_type),
+        }
+    }
+}
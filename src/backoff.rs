@@ -0,0 +1,152 @@
+use std::time::Duration;
+
+use reqwest::StatusCode;
+
+use crate::Error;
+
+/// Exponential delay used between requests while waiting on Snowflake.
+///
+/// The n:th delay is `initial * factor^n`, capped at `max`.
+#[derive(Clone, Copy, Debug)]
+pub struct Backoff {
+    pub initial: Duration,
+    pub max: Duration,
+    pub factor: u32,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_millis(250),
+            max: Duration::from_secs(5),
+            factor: 2,
+        }
+    }
+}
+
+impl Backoff {
+    pub fn delay(&self, attempt: u32) -> Duration {
+        self.initial
+            .saturating_mul(self.factor.saturating_pow(attempt))
+            .min(self.max)
+    }
+}
+
+/// How requests failing with transient errors are retried.
+///
+/// Connection errors, including failures while reading a response body,
+/// and responses with a status in `retry_statuses` are retried.
+/// Statements are resubmitted with the same request id, which Snowflake treats as idempotent.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// Attempts in total, including the first one.
+    pub max_attempts: u32,
+    pub backoff: Backoff,
+    /// Randomizes each delay to between half and all of the backoff delay.
+    pub jitter: bool,
+    pub retry_statuses: Vec<StatusCode>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            backoff: Backoff {
+                initial: Duration::from_millis(500),
+                max: Duration::from_secs(10),
+                factor: 2,
+            },
+            jitter: true,
+            retry_statuses: vec![
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let delay = self.backoff.delay(attempt);
+
+        if self.jitter {
+            delay.mul_f64(0.5 + fastrand::f64() / 2.0)
+        } else {
+            delay
+        }
+    }
+
+    pub fn is_retryable(&self, err: &Error) -> bool {
+        match err {
+            // Reading a body through reqwest's decoder reports failures as decode errors.
+            Error::Http(err) => {
+                err.is_connect()
+                    || err.is_timeout()
+                    || err.is_request()
+                    || err.is_body()
+                    || err.is_decode()
+            }
+            Error::Snowflake(err) => self.retry_statuses.contains(&err.status),
+            Error::NokResponse { status, .. } => self.retry_statuses.contains(status),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn delay_grows_until_max() {
+        let backoff = Backoff {
+            initial: Duration::from_millis(100),
+            max: Duration::from_secs(1),
+            factor: 3,
+        };
+
+        assert_eq!(backoff.delay(0), Duration::from_millis(100));
+        assert_eq!(backoff.delay(1), Duration::from_millis(300));
+        assert_eq!(backoff.delay(2), Duration::from_millis(900));
+        assert_eq!(backoff.delay(3), Duration::from_secs(1));
+        assert_eq!(backoff.delay(u32::MAX), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn jitter_stays_within_half_of_delay() {
+        let policy = RetryPolicy::default();
+
+        for attempt in 0..5 {
+            let max = policy.backoff.delay(attempt);
+            let delay = policy.delay(attempt);
+
+            assert!(max / 2 <= delay && delay <= max);
+        }
+    }
+
+    #[test]
+    fn retryable_statuses() {
+        let policy = RetryPolicy::default();
+
+        let nok = |status| Error::NokResponse {
+            status,
+            body: String::new(),
+        };
+
+        assert!(policy.is_retryable(&nok(StatusCode::SERVICE_UNAVAILABLE)));
+        assert!(policy.is_retryable(&nok(StatusCode::TOO_MANY_REQUESTS)));
+        assert!(!policy.is_retryable(&nok(StatusCode::UNPROCESSABLE_ENTITY)));
+        assert!(!policy.is_retryable(&Error::InternalMutexError));
+    }
+}
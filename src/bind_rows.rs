@@ -0,0 +1,286 @@
+use std::collections::HashMap;
+
+use snowsql_deserialize::{BindingKind, BindingValue};
+
+use crate::{Binding, BindingError};
+
+/// A row of values bound with [`crate::QueryBuilder::bind_rows`].
+///
+/// Fails when a value cannot be converted, e.g. a [`crate::Json`] that does not serialize.
+pub trait BindRow {
+    fn bind_row(&self) -> Result<Vec<BindingValue>, BindingError>;
+}
+
+impl<T> BindRow for &T
+where
+    T: BindRow + ?Sized,
+{
+    fn bind_row(&self) -> Result<Vec<BindingValue>, BindingError> {
+        T::bind_row(self)
+    }
+}
+
+impl BindRow for [BindingValue] {
+    fn bind_row(&self) -> Result<Vec<BindingValue>, BindingError> {
+        Ok(self.to_vec())
+    }
+}
+
+impl BindRow for Vec<BindingValue> {
+    fn bind_row(&self) -> Result<Vec<BindingValue>, BindingError> {
+        Ok(self.clone())
+    }
+}
+
+impl<const N: usize> BindRow for [BindingValue; N] {
+    fn bind_row(&self) -> Result<Vec<BindingValue>, BindingError> {
+        Ok(self.to_vec())
+    }
+}
+
+macro_rules! impl_bind_row_for_tuple {
+    ($($ty: ident),+) => {
+        impl<$($ty),+> BindRow for ($($ty,)+)
+        where
+            $($ty: Clone + TryInto<BindingValue>, BindingError: From<$ty::Error>),+
+        {
+            #[allow(non_snake_case)]
+            fn bind_row(&self) -> Result<Vec<BindingValue>, BindingError> {
+                let ($($ty,)+) = self;
+                Ok(vec![$($ty.clone().try_into()?),+])
+            }
+        }
+    };
+}
+
+impl_bind_row_for_tuple!(A);
+impl_bind_row_for_tuple!(A, B);
+impl_bind_row_for_tuple!(A, B, C);
+impl_bind_row_for_tuple!(A, B, C, D);
+impl_bind_row_for_tuple!(A, B, C, D, E);
+impl_bind_row_for_tuple!(A, B, C, D, E, F);
+impl_bind_row_for_tuple!(A, B, C, D, E, F, G);
+impl_bind_row_for_tuple!(A, B, C, D, E, F, G, H);
+impl_bind_row_for_tuple!(A, B, C, D, E, F, G, H, I);
+impl_bind_row_for_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_bind_row_for_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_bind_row_for_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+/// Turns rows into one array binding per column.
+pub(crate) fn column_bindings(
+    rows: &[Vec<BindingValue>],
+) -> Result<HashMap<String, Binding>, BindingError> {
+    let column_count = rows.first().map(Vec::len).unwrap_or_default();
+
+    // kind of each column, and whether it was only taken from a null
+    let mut kinds = vec![None::<(BindingKind, bool)>; column_count];
+    let mut columns = vec![Vec::with_capacity(rows.len()); column_count];
+
+    for (row_idx, row) in rows.iter().enumerate() {
+        if row.len() != column_count {
+            return Err(BindingError::RowLength {
+                row: row_idx,
+                expected: column_count,
+                actual: row.len(),
+            });
+        }
+
+        for (col_idx, value) in row.iter().enumerate() {
+            let kind = value.kind();
+            let is_null = matches!(value, BindingValue::Null(_));
+
+            match kinds[col_idx] {
+                None => kinds[col_idx] = Some((kind, is_null)),
+                // Nulls of another kind are nulls all the same.
+                Some(_) if is_null => {}
+                Some((_, true)) => kinds[col_idx] = Some((kind, false)),
+                Some((prev, false)) if prev == kind => {}
+                Some(_) => return Err(BindingError::MixedKinds { column: col_idx }),
+            }
+
+            columns[col_idx].push(value.value());
+        }
+    }
+
+    Ok(kinds
+        .into_iter()
+        .zip(columns)
+        .enumerate()
+        .map(|(i, (kind, values))| {
+            (
+                (i + 1).to_string(),
+                Binding::array(kind.map_or(BindingKind::Text, |(kind, _)| kind), values),
+            )
+        })
+        .collect())
+}
+
+/// Splits rows into chunks whose values, plus `row_overhead` bytes per row,
+/// add up to at most `max_bytes`, always putting at least one row in a chunk.
+pub(crate) fn chunk(
+    rows: Vec<Vec<BindingValue>>,
+    max_bytes: usize,
+    row_overhead: usize,
+) -> Vec<Vec<Vec<BindingValue>>> {
+    let mut chunks = Vec::new();
+    let mut current = Vec::new();
+    let mut current_bytes = 0;
+
+    for row in rows {
+        let row_bytes = row_size(&row) + row_overhead;
+
+        if !current.is_empty() && max_bytes < current_bytes + row_bytes {
+            chunks.push(std::mem::take(&mut current));
+            current_bytes = 0;
+        }
+
+        current_bytes += row_bytes;
+        current.push(row);
+    }
+
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+
+    chunks
+}
+
+/// Snowflake allows at most this many rows in a `VALUES` clause.
+const MAX_VALUES_ROWS: usize = 16_384;
+
+/// Upper bound of the JSON around a positional binding's value besides its index,
+/// e.g. `"":{"type":"TIMESTAMP_NTZ","value":}`, the comma being counted by [`row_size`].
+const BINDING_JSON: usize = 36;
+
+/// Upper bound of the JSON around each value when at most `binding_count` values are bound,
+/// e.g. `"16384":{"type":"TIMESTAMP_NTZ","value":}` for up to 99999 values.
+pub(crate) fn binding_overhead(binding_count: usize) -> usize {
+    BINDING_JSON + binding_count.max(1).to_string().len()
+}
+
+/// Splits rows bound one value at a time into statements of at most `max_bytes`,
+/// for statements with a `VALUES (?, ...), ...` row per row.
+///
+/// `statement_len` is the length of the statement without rows,
+/// each row adds its `(?, ...), ` and the bindings of its values.
+pub(crate) fn values_chunks(
+    rows: Vec<Vec<BindingValue>>,
+    max_bytes: usize,
+    statement_len: usize,
+) -> Vec<Vec<Vec<BindingValue>>> {
+    let columns = rows.first().map_or(0, Vec::len);
+    let max_bindings = rows.len().min(MAX_VALUES_ROWS) * columns;
+    let row_overhead = 3 * columns + ", ".len() + columns * binding_overhead(max_bindings);
+
+    chunk(rows, max_bytes.saturating_sub(statement_len), row_overhead)
+        .into_iter()
+        .flat_map(|chunk| {
+            chunk
+                .chunks(MAX_VALUES_ROWS)
+                .map(<[_]>::to_vec)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Size of the row's values when serialized in a JSON array.
+pub(crate) fn row_size(row: &[BindingValue]) -> usize {
+    row.iter()
+        .map(|value| match value {
+            BindingValue::Null(_) => "null,".len(),
+            value => value.to_string().len() + r#""","#.len(),
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn builds_column_arrays() {
+        let rows = [(1_i64, "a", Some(1.5_f64)), (2, "b", None)]
+            .iter()
+            .map(BindRow::bind_row)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        let bindings = column_bindings(&rows).expect("binding rows");
+
+        assert_eq!(
+            serde_json::to_value(&bindings).expect("serializing"),
+            serde_json::json!({
+                "1": { "type": "FIXED", "value": ["1", "2"] },
+                "2": { "type": "TEXT", "value": ["a", "b"] },
+                "3": { "type": "REAL", "value": ["1.5", null] },
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_uneven_rows_and_mixed_kinds() {
+        let uneven = vec![vec![1.into(), 2.into()], vec![3.into()]];
+        assert!(matches!(
+            column_bindings(&uneven),
+            Err(BindingError::RowLength {
+                row: 1,
+                expected: 2,
+                actual: 1
+            })
+        ));
+
+        let mixed = vec![vec![1.into()], vec!["a".into()]];
+        assert!(matches!(
+            column_bindings(&mixed),
+            Err(BindingError::MixedKinds { column: 0 })
+        ));
+    }
+
+    #[test]
+    fn chunks_by_size() {
+        let rows = (0..10)
+            .map(|i| vec![BindingValue::from(format!("{i:08}"))])
+            .collect::<Vec<_>>();
+
+        // each row is 8 characters plus quotes and a comma
+        let chunks = chunk(rows.clone(), 35, 0);
+
+        assert_eq!(
+            chunks.iter().map(Vec::len).collect::<Vec<_>>(),
+            [3, 3, 3, 1]
+        );
+
+        // with 6 more bytes a row, only 2 rows fit
+        let chunks = chunk(rows, 35, 6);
+
+        assert_eq!(
+            chunks.iter().map(Vec::len).collect::<Vec<_>>(),
+            [2, 2, 2, 2, 2]
+        );
+    }
+
+    #[test]
+    fn values_chunks_count_six_digit_indices() {
+        let columns = 10;
+        let row = vec![BindingValue::TimestampNtz("2024-01-01 00:00:00.000000000".into()); columns];
+        let rows = vec![row; MAX_VALUES_ROWS];
+
+        // bindings as sent, plus each row's `(?, ...), `
+        let payload = |rows: &[Vec<BindingValue>]| {
+            let bindings = crate::positional_bindings(rows.iter().flatten().cloned());
+            serde_json::to_string(&bindings).unwrap().len() + rows.len() * (3 * columns + 2)
+        };
+
+        // more than 99999 bindings, which no longer fit once their indices are counted
+        assert!(100_000 < MAX_VALUES_ROWS * columns);
+        let max_bytes = payload(&rows) - 1;
+
+        let chunks = values_chunks(rows, max_bytes, 0);
+
+        assert_eq!(chunks.len(), 2);
+        for chunk in chunks {
+            assert!(payload(&chunk) <= max_bytes);
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use tokio_util::sync::CancellationToken;
+
+use crate::Client;
+
+/// When to cancel a statement that is still running on Snowflake.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Cancellation {
+    pub on_drop: bool,
+    pub token: Option<CancellationToken>,
+}
+
+impl Cancellation {
+    pub fn is_enabled(&self) -> bool {
+        self.on_drop || self.token.is_some()
+    }
+}
+
+/// Cancels the statement if dropped before being disarmed.
+pub(crate) struct CancelGuard {
+    client: Client,
+    statement_handle: Option<String>,
+}
+
+impl CancelGuard {
+    pub fn new(client: &Client, statement_handle: &str) -> Self {
+        Self {
+            client: client.clone(),
+            statement_handle: Some(statement_handle.into()),
+        }
+    }
+
+    pub fn disarm(mut self) {
+        self.statement_handle = None;
+    }
+}
+
+impl Drop for CancelGuard {
+    fn drop(&mut self) {
+        let Some(statement_handle) = self.statement_handle.take() else {
+            return;
+        };
+
+        // Dropping outside of a runtime leaves nothing to run the request on.
+        let Ok(rt) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+
+        let client = self.client.clone();
+
+        rt.spawn(async move {
+            let _ = client.cancel(&statement_handle).await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    use snowsql_deserialize::RawRow;
+    use tokio_util::sync::CancellationToken;
+
+    use crate::{
+        mock::{MockResponse, MockServer},
+        sql, Error,
+    };
+
+    /// Answers submissions and the cancel request, keeping the statement running
+    /// until `complete_after` polls.
+    fn statement(complete_after: usize) -> impl Fn(&str) -> MockResponse + Send + Sync {
+        let polls = AtomicUsize::new(0);
+
+        move |path| {
+            if path.ends_with("/statements/h1/cancel") {
+                MockResponse::new(200, r#"{"statementHandle": "h1"}"#)
+            } else if path.contains("/statements?") {
+                MockResponse::new(202, r#"{"statementHandle": "h1"}"#)
+            } else if polls.fetch_add(1, Ordering::SeqCst) < complete_after {
+                MockResponse::new(202, r#"{"statementHandle": "h1"}"#)
+                    .delayed(Duration::from_millis(5))
+            } else {
+                MockResponse::new(200, r#"{"statementHandle": "h1"}"#)
+            }
+        }
+    }
+
+    fn cancel_requests(server: &MockServer) -> usize {
+        server
+            .requests()
+            .iter()
+            .filter(|req| req.starts_with("POST ") && req.ends_with("/statements/h1/cancel"))
+            .count()
+    }
+
+    #[tokio::test]
+    async fn token_cancels_running_statement() {
+        let server = MockServer::start(statement(usize::MAX)).await;
+        let token = CancellationToken::new();
+
+        let cancel = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            cancel.cancel();
+        });
+
+        let err = sql::<RawRow>("SELECT 1")
+            .with_cancellation_token(token)
+            .text(&server.client())
+            .await
+            .expect_err("cancelled");
+
+        assert!(matches!(
+            err,
+            Error::Cancelled { ref statement_handle, cancel_error: None } if statement_handle == "h1"
+        ));
+        assert_eq!(cancel_requests(&server), 1);
+    }
+
+    #[tokio::test]
+    async fn token_reports_failed_cancel_request() {
+        let server = MockServer::start(|path: &str| {
+            if path.ends_with("/cancel") {
+                MockResponse::new(500, "unavailable")
+            } else {
+                MockResponse::new(202, r#"{"statementHandle": "h1"}"#)
+            }
+        })
+        .await;
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let err = sql::<RawRow>("SELECT 1")
+            .with_cancellation_token(token)
+            .text(&server.client())
+            .await
+            .expect_err("cancelled");
+
+        assert!(matches!(
+            err,
+            Error::Cancelled {
+                cancel_error: Some(_),
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn dropping_query_sends_cancel() {
+        let server = MockServer::start(statement(usize::MAX)).await;
+        let c = server.client();
+
+        let query = sql::<RawRow>("SELECT 1").cancel_on_drop().text(&c);
+        tokio::time::timeout(Duration::from_millis(20), query)
+            .await
+            .expect_err("still running");
+
+        // the cancel request is sent from a spawned task
+        for _ in 0..100 {
+            if cancel_requests(&server) > 0 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert_eq!(cancel_requests(&server), 1);
+    }
+
+    #[tokio::test]
+    async fn completed_query_sends_no_cancel() {
+        let server = MockServer::start(statement(2)).await;
+
+        sql::<RawRow>("SELECT 1")
+            .cancel_on_drop()
+            .text(&server.client())
+            .await
+            .expect("completing");
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        assert_eq!(cancel_requests(&server), 0);
+        // submission, two running polls and the result
+        assert_eq!(server.requests().len(), 4);
+    }
+}
@@ -1,15 +1,30 @@
 use std::{
-    sync::{Arc, Mutex},
+    future::Future,
+    sync::{Arc, Mutex, PoisonError},
     time,
 };
 
+use bytes::Bytes;
 use reqwest::{
     header::{HeaderName, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE, USER_AGENT},
-    Method,
+    Method, StatusCode,
 };
-use snowsql_deserialize::RawRow;
+use serde::de::IgnoredAny;
+use snowsql_deserialize::{FromRow, RawRow, Row, Schema};
 
-use crate::{jwt, Error, PrivateKey, PublicKey, Result};
+use crate::{
+    cancel::{CancelGuard, Cancellation},
+    jwt,
+    statement::QueryStatus,
+    Backoff, Error, PrivateKey, PublicKey, Response, ResponseOk, Result, RetryPolicy,
+    SnowflakeQuery,
+};
+
+/// A response whose body has been read in full, see [`Client::send`].
+pub(crate) struct ResponseBody {
+    pub status: StatusCode,
+    pub body: Bytes,
+}
 
 #[derive(Clone)]
 pub struct Client(Arc<Mutex<ClientInner>>);
@@ -19,6 +34,8 @@ pub struct ClientInner {
     jwt: Jwt,
     host: String,
     http: reqwest::Client,
+    poll_backoff: Backoff,
+    retry_policy: RetryPolicy,
 }
 
 struct Jwt {
@@ -99,11 +116,64 @@ impl Client {
             host: format!("https://{}.snowflakecomputing.com/api/v2/", host.as_ref()),
             jwt,
             http,
+            poll_backoff: Backoff::default(),
+            retry_policy: RetryPolicy::default(),
         };
 
         Ok(Self(Arc::new(Mutex::new(inner))))
     }
 
+    /// A client sending requests to `base_url`, with a token that is never refreshed.
+    #[cfg(test)]
+    pub(crate) fn new_with_base_url(base_url: String) -> Self {
+        let inner = ClientInner {
+            credentials: Credentials {
+                private_key: PrivateKey(String::new()),
+                public_key: PublicKey(String::new()),
+                account_identifier: String::new(),
+                user: String::new(),
+            },
+            host: base_url,
+            jwt: Jwt {
+                token: "test".into(),
+                expires_at: time::Instant::now() + time::Duration::from_secs(60 * 60),
+            },
+            http: reqwest::Client::new(),
+            poll_backoff: Backoff::default(),
+            retry_policy: RetryPolicy::default(),
+        };
+
+        Self(Arc::new(Mutex::new(inner)))
+    }
+
+    /// Sets the delay between status requests while waiting on a statement
+    /// that Snowflake executes asynchronously.
+    pub fn with_poll_backoff(self, backoff: Backoff) -> Self {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .poll_backoff = backoff;
+        self
+    }
+
+    /// Sets how requests failing with transient errors are retried.
+    pub fn with_retry_policy(self, retry_policy: RetryPolicy) -> Self {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retry_policy = retry_policy;
+        self
+    }
+
+    fn retry_policy(&self) -> Result<RetryPolicy> {
+        Ok(self
+            .0
+            .lock()
+            .map_err(|_| Error::InternalMutexError)?
+            .retry_policy
+            .clone())
+    }
+
     pub(crate) fn new_request(
         &self,
         method: reqwest::Method,
@@ -133,11 +203,213 @@ impl Client {
         )
     }
 
-    pub(crate) fn post(&self) -> Result<reqwest::RequestBuilder> {
-        self.new_request(
+    pub(crate) fn get_statement(&self, statement_handle: &str) -> Result<reqwest::RequestBuilder> {
+        self.new_request(Method::GET, &format!("/statements/{}", statement_handle))
+    }
+
+    /// Retries of a statement must reuse its `request_id`.
+    pub(crate) fn post(
+        &self,
+        request_id: &uuid::Uuid,
+        retry: bool,
+    ) -> Result<reqwest::RequestBuilder> {
+        let req = self.new_request(
             Method::POST,
-            &format!("/statements?requestId={}", uuid::Uuid::new_v4()),
-        )
+            &format!("/statements?requestId={}", request_id),
+        )?;
+
+        Ok(if retry {
+            req.query(&[("retry", "true")])
+        } else {
+            req
+        })
+    }
+
+    /// Submits a statement without waiting for it to finish.
+    pub(crate) fn post_async(
+        &self,
+        request_id: &uuid::Uuid,
+        retry: bool,
+    ) -> Result<reqwest::RequestBuilder> {
+        Ok(self.post(request_id, retry)?.query(&[("async", "true")]))
+    }
+
+    /// Runs `f` until it succeeds or fails with an error
+    /// the retry policy does not consider transient.
+    ///
+    /// `f` is told whether the attempt is a retry.
+    pub(crate) async fn retry<T, F, Fut>(&self, mut f: F) -> Result<T>
+    where
+        F: FnMut(bool) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let policy = self.retry_policy()?;
+        let mut attempt = 0;
+
+        loop {
+            match f(0 < attempt).await {
+                Err(err) if attempt + 1 < policy.max_attempts && policy.is_retryable(&err) => {
+                    tokio::time::sleep(policy.delay(attempt)).await;
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+
+    /// Sends the request built by `build` and reads its body, retrying transient failures.
+    ///
+    /// The body is read within the retried attempt,
+    /// so that a connection failing while reading it is retried too.
+    pub(crate) async fn send(
+        &self,
+        build: impl Fn(bool) -> Result<reqwest::RequestBuilder>,
+    ) -> Result<ResponseBody> {
+        let retry_statuses = &self.retry_policy()?.retry_statuses;
+        let build = &build;
+
+        self.retry(|retry| async move {
+            let res = build(retry)?.send().await?;
+            let status = res.status();
+            let body = res.bytes().await?;
+
+            if retry_statuses.contains(&status) {
+                return Err(crate::nok_error(status, &body));
+            }
+
+            Ok(ResponseBody { status, body })
+        })
+        .await
+    }
+
+    /// Submits a statement and waits for it to finish.
+    ///
+    /// With cancellation enabled the statement is submitted asynchronously,
+    /// so that its handle is known while waiting.
+    /// Until Snowflake answers the submission there is no handle to cancel:
+    /// dropping the future before then leaves the statement running.
+    pub(crate) async fn execute(
+        &self,
+        query: &SnowflakeQuery,
+        cancellation: &Cancellation,
+    ) -> Result<ResponseBody> {
+        let request_id = uuid::Uuid::new_v4();
+
+        if !cancellation.is_enabled() {
+            let res = self
+                .send(|retry| Ok(self.post(&request_id, retry)?.json(query)))
+                .await?;
+            return self.wait_until_complete(res).await;
+        }
+
+        let statement_handle = self
+            .send(|retry| Ok(self.post_async(&request_id, retry)?.json(query)))
+            .await?
+            .snowflake_response::<QueryStatus>()
+            .await?
+            .statement_handle;
+
+        let guard = cancellation
+            .on_drop
+            .then(|| CancelGuard::new(self, &statement_handle));
+
+        let wait = self.fetch_statement(&statement_handle);
+
+        let res = match cancellation.token.as_ref() {
+            None => Some(wait.await),
+            Some(token) => tokio::select! {
+                res = wait => Some(res),
+                () = token.cancelled() => None,
+            },
+        };
+
+        if let Some(guard) = guard {
+            guard.disarm();
+        }
+
+        match res {
+            Some(res) => res,
+            None => {
+                let cancel_error = self.cancel(&statement_handle).await.err().map(Box::new);
+                Err(Error::Cancelled {
+                    statement_handle,
+                    cancel_error,
+                })
+            }
+        }
+    }
+
+    /// Fetches the result of a previously executed statement, without resubmitting it.
+    ///
+    /// Waits for the statement if it is still running.
+    /// The remaining partitions are available through [`Response::partitions`].
+    pub async fn fetch<R>(&self, statement_handle: impl AsRef<str>) -> Result<Response<Row<R>>>
+    where
+        R: FromRow,
+    {
+        self.fetch_statement(statement_handle.as_ref())
+            .await?
+            .snowflake_rows(None)
+            .await
+    }
+
+    /// Like [`Self::fetch`], checking the result's columns against `schema` before parsing any row,
+    /// see [`crate::QueryBuilder::with_schema_validation`].
+    pub async fn fetch_validated<R>(
+        &self,
+        statement_handle: impl AsRef<str>,
+        schema: &Schema,
+    ) -> Result<Response<Row<R>>>
+    where
+        R: FromRow,
+    {
+        self.fetch_statement(statement_handle.as_ref())
+            .await?
+            .snowflake_rows(Some(schema))
+            .await
+    }
+
+    /// Gets a statement and waits for it to finish.
+    pub(crate) async fn fetch_statement(&self, statement_handle: &str) -> Result<ResponseBody> {
+        let res = self.send(|_| self.get_statement(statement_handle)).await?;
+        self.wait_until_complete(res).await
+    }
+
+    /// Cancels a running statement.
+    pub async fn cancel(&self, statement_handle: impl AsRef<str>) -> Result<()> {
+        let path = format!("/statements/{}/cancel", statement_handle.as_ref());
+
+        self.send(|_| self.new_request(Method::POST, &path))
+            .await?
+            .snowflake_response::<IgnoredAny>()
+            .await?;
+
+        Ok(())
+    }
+
+    /// Statements running longer than ~45 seconds are answered with `202 Accepted`.
+    /// Poll the statement until Snowflake answers with anything else.
+    pub(crate) async fn wait_until_complete(&self, mut res: ResponseBody) -> Result<ResponseBody> {
+        let backoff = self
+            .0
+            .lock()
+            .map_err(|_| Error::InternalMutexError)?
+            .poll_backoff;
+
+        let mut attempt = 0;
+
+        while res.status == StatusCode::ACCEPTED {
+            let status = res.snowflake_response::<QueryStatus>().await?;
+
+            tokio::time::sleep(backoff.delay(attempt)).await;
+            attempt = attempt.saturating_add(1);
+
+            res = self
+                .send(|_| self.get_statement(&status.statement_handle))
+                .await?;
+        }
+
+        Ok(res)
     }
 
     pub async fn verify(&self) -> Result<bool> {
@@ -145,3 +417,103 @@ impl Client {
         Ok(!res.data.is_empty())
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use snowsql_deserialize::RawRow;
+
+    use crate::{
+        mock::{in_order, MockResponse, MockServer},
+        sql,
+    };
+
+    fn complete() -> MockResponse {
+        MockResponse::new(200, r#"{"statementHandle": "h1"}"#)
+    }
+
+    fn unavailable() -> MockResponse {
+        MockResponse::new(503, "unavailable")
+    }
+
+    #[tokio::test]
+    async fn retried_statement_keeps_request_id() {
+        let server = MockServer::start(in_order([unavailable(), unavailable(), complete()])).await;
+
+        sql::<RawRow>("SELECT 1")
+            .text(&server.retrying_client())
+            .await
+            .expect("retrying");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+
+        let (first, request_id) = requests[0].split_once("requestId=").unwrap();
+        assert!(first.starts_with("POST ") && !request_id.contains("retry"));
+        for retry in &requests[1..] {
+            assert_eq!(
+                retry.split_once("requestId=").unwrap().1,
+                format!("{request_id}&retry=true")
+            );
+        }
+
+        // the statement is resubmitted unchanged
+        let bodies = server.bodies();
+        assert!(bodies.iter().all(|body| *body == bodies[0]));
+    }
+
+    #[tokio::test]
+    async fn failed_body_read_is_retried() {
+        let server = MockServer::start(in_order([complete().truncated(), complete()])).await;
+
+        let text = sql::<RawRow>("SELECT 1")
+            .text(&server.retrying_client())
+            .await
+            .expect("retrying");
+
+        assert_eq!(text, r#"{"statementHandle": "h1"}"#);
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn non_retryable_status_is_not_retried() {
+        let server = MockServer::start(in_order([
+            MockResponse::new(
+                422,
+                r#"{"code": "001003", "message": "syntax error", "sqlState": "42000"}"#,
+            ),
+            complete(),
+        ]))
+        .await;
+
+        let err = sql::<RawRow>("SELEC 1")
+            .query(&server.retrying_client())
+            .await
+            .expect_err("syntax error");
+
+        assert_eq!(err.as_snowflake().unwrap().code, "001003");
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn retries_stop_after_max_attempts() {
+        let server = MockServer::start(in_order([
+            unavailable(),
+            unavailable(),
+            unavailable(),
+            complete(),
+        ]))
+        .await;
+
+        let err = sql::<RawRow>("SELECT 1")
+            .text(&server.retrying_client())
+            .await
+            .expect_err("unavailable");
+
+        assert!(matches!(
+            err,
+            crate::Error::NokResponse { status, .. } if status == 503
+        ));
+        assert_eq!(server.requests().len(), 3);
+    }
+}
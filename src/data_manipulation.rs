@@ -1,19 +1,37 @@
 use serde::Deserialize;
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Default)]
 pub struct Changes {
-    #[serde(rename = "numRowsInserted")]
+    #[serde(rename = "numRowsInserted", default)]
     pub rows_inserted: usize,
-    #[serde(rename = "numRowsDeleted")]
+    #[serde(rename = "numRowsDeleted", default)]
     pub rows_deleted: usize,
-    #[serde(rename = "numRowsUpdated")]
+    #[serde(rename = "numRowsUpdated", default)]
     pub rows_updated: usize,
-    #[serde(rename = "numDmlDuplicates")]
+    #[serde(rename = "numDmlDuplicates", default)]
     pub duplicates: usize,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Default)]
 pub struct DataManipulationResult {
     pub message: String,
     pub stats: Changes,
 }
+
+impl DataManipulationResult {
+    /// Combines the results of statements run one after another.
+    pub fn merge(mut self, other: Self) -> Self {
+        self.message = other.message;
+        self.stats += other.stats;
+        self
+    }
+}
+
+impl std::ops::AddAssign for Changes {
+    fn add_assign(&mut self, other: Self) {
+        self.rows_inserted += other.rows_inserted;
+        self.rows_deleted += other.rows_deleted;
+        self.rows_updated += other.rows_updated;
+        self.duplicates += other.duplicates;
+    }
+}
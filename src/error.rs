@@ -7,12 +7,18 @@ pub enum Error {
     #[error("invalid credentials: {0}")]
     Credentials(#[from] CredentialsError),
 
+    #[error("invalid bindings: {0}")]
+    Binding(#[from] BindingError),
+
     #[error("http: {0}")]
     Http(#[from] reqwest::Error),
 
     #[error("Nok response `{status}` with body:\n{body}")]
     NokResponse { status: StatusCode, body: String },
 
+    #[error("snowflake: {0}")]
+    Snowflake(SnowflakeError),
+
     #[error("parsing snowflake response: {err} with body:\n{body}")]
     DeserializeSnowflakeResponse {
         err: serde_json::Error,
@@ -22,6 +28,29 @@ pub enum Error {
     #[error("deserialize: {0}")]
     Deserialize(#[from] snowsql_deserialize::Error),
 
+    #[error("result does not match the schema: {0}")]
+    Schema(#[from] snowsql_deserialize::SchemaMismatch),
+
+    #[error("partition {index}: {err}")]
+    Partition { index: usize, err: Box<Error> },
+
+    #[error("saved state has {expected} partitions, the result has {actual}")]
+    PartitionCount { expected: usize, actual: usize },
+
+    /// `cancel_error` is set if Snowflake could not be asked to cancel the statement,
+    /// which may then still be running.
+    #[error(
+        "statement `{statement_handle}` was cancelled{}",
+        .cancel_error
+            .as_ref()
+            .map(|err| format!(", but cancelling it on Snowflake failed: {err}"))
+            .unwrap_or_default()
+    )]
+    Cancelled {
+        statement_handle: String,
+        cancel_error: Option<Box<Error>>,
+    },
+
     #[error("Internal Mutex error")]
     InternalMutexError,
 }
@@ -36,3 +65,97 @@ pub enum CredentialsError {
     #[error("error when creating token: {0}")]
     Token(Cow<'static, str>),
 }
+
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum BindingError {
+    #[error("no value bound to placeholder `:{0}`")]
+    MissingValue(String),
+
+    #[error("value bound to `{0}` has no placeholder in the statement")]
+    UnusedValue(String),
+
+    #[error("positional, named and row bindings cannot be combined")]
+    Mixed,
+
+    #[error("row {row} has {actual} values, expected {expected}")]
+    RowLength {
+        row: usize,
+        expected: usize,
+        actual: usize,
+    },
+
+    #[error("column {column} contains values of different types")]
+    MixedKinds { column: usize },
+
+    #[error("serializing JSON: {0}")]
+    Json(String),
+}
+
+impl From<serde_json::Error> for BindingError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err.to_string())
+    }
+}
+
+/// Lets conversions that cannot fail be used where a [`BindingError`] is expected.
+impl From<std::convert::Infallible> for BindingError {
+    fn from(never: std::convert::Infallible) -> Self {
+        match never {}
+    }
+}
+
+impl Error {
+    /// The error returned by Snowflake, if any.
+    pub fn as_snowflake(&self) -> Option<&SnowflakeError> {
+        match self {
+            Self::Snowflake(err) => Some(err),
+            Self::Partition { err, .. } => err.as_snowflake(),
+            _ => None,
+        }
+    }
+}
+
+/// Error body returned by the SQL API when a request or statement fails.
+///
+/// For error codes, see
+/// [Handling Errors](https://docs.snowflake.com/developer-guide/sql-api/handling-responses#handling-errors).
+#[derive(serde::Deserialize, Debug, Clone, thiserror::Error)]
+#[serde(rename_all = "camelCase")]
+#[error("{code} ({}): {message}", .sql_state.as_deref().unwrap_or("-"))]
+pub struct SnowflakeError {
+    #[serde(skip)]
+    pub status: StatusCode,
+    pub code: String,
+    pub message: String,
+    pub sql_state: Option<String>,
+    pub statement_handle: Option<String>,
+}
+
+impl SnowflakeError {
+    /// The statement could not be parsed.
+    ///
+    /// sqlState `42000` also covers access rule violations, so only the error code is checked,
+    /// besides the standard syntax error state `42601`.
+    pub fn is_syntax_error(&self) -> bool {
+        self.code == "001003" || self.sql_state.as_deref() == Some("42601")
+    }
+
+    /// The object does not exist, or the role is not authorized to use it.
+    pub fn is_object_not_found(&self) -> bool {
+        matches!(self.code.as_str(), "002003" | "002043")
+            || self.sql_state.as_deref() == Some("02000")
+    }
+
+    pub fn is_auth_failure(&self) -> bool {
+        matches!(
+            self.status,
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN
+        ) || self.code.starts_with("390")
+            || self.sql_state.as_deref() == Some("08001")
+    }
+
+    /// The statement exceeded its timeout, see [`crate::QueryBuilder::with_timeout`].
+    pub fn is_timeout(&self) -> bool {
+        self.status == StatusCode::REQUEST_TIMEOUT || self.code == "000630"
+    }
+}
@@ -0,0 +1,148 @@
+use snowsql_deserialize::RawRow;
+
+use crate::{bind_rows::values_chunks, data_manipulation::DataManipulationResult, BindRow};
+
+#[allow(async_fn_in_trait)]
+pub trait Insertable
+where
+    Self: Sized,
+    Self: BindRow,
+{
+    /// `INSERT INTO table (cols...) VALUES (?, ...)`, leaving out fields marked `skip_insert`.
+    ///
+    /// With `json` fields the derive writes `INSERT INTO table (cols...) SELECT column1, PARSE_JSON(column2), ... FROM VALUES (?, ...)` instead.
+    const INSERT: &str;
+
+    /// The `(?, ...)` row of [`Self::INSERT`], repeated for each row with every value bound on its own,
+    /// or `None` to bind rows with array bindings.
+    ///
+    /// Only set by the derive with `json` fields, since Snowflake only takes array bindings in `INSERT ... VALUES`.
+    /// Not part of the public API: it must match the `VALUES` row of the derived [`Self::INSERT`].
+    #[doc(hidden)]
+    const INSERT_VALUES_ROW: Option<&str> = None;
+
+    /// [`Self::INSERT`] with placeholders for `row_count` rows, see `INSERT_VALUES_ROW`.
+    #[doc(hidden)]
+    fn insert_statement(row_count: usize) -> String {
+        let mut statement = Self::INSERT.to_string();
+
+        if let Some(row) = Self::INSERT_VALUES_ROW {
+            for _ in 1..row_count {
+                statement.push_str(", ");
+                statement.push_str(row);
+            }
+        }
+
+        statement
+    }
+
+    async fn insert(&self, c: &crate::Client) -> crate::Result<DataManipulationResult> {
+        Self::insert_many(c, std::slice::from_ref(self)).await
+    }
+
+    /// Inserts all rows with array bindings, or in `VALUES` rows bound one value at a time
+    /// for derived structs with `json` fields, split over several requests if the rows are too large for one.
+    async fn insert_many(
+        c: &crate::Client,
+        rows: &[Self],
+    ) -> crate::Result<DataManipulationResult> {
+        if rows.is_empty() {
+            return Ok(DataManipulationResult::default());
+        }
+
+        if Self::INSERT_VALUES_ROW.is_none() {
+            return crate::sql::<RawRow>(Self::INSERT)
+                .bind_rows(rows)
+                .manipulate(c)
+                .await;
+        }
+
+        let rows = rows
+            .iter()
+            .map(BindRow::bind_row)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut total = DataManipulationResult::default();
+
+        for rows in values_chunks(rows, crate::DEFAULT_MAX_PAYLOAD_BYTES, Self::INSERT.len()) {
+            let qry = rows.iter().flatten().fold(
+                crate::sql::<RawRow>(Self::insert_statement(rows.len())),
+                |qry, value| qry.add_binding(value.clone()),
+            );
+
+            total = total.merge(qry.manipulate(c).await?);
+        }
+
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use snowsql_deserialize::{BindingValue, Json};
+
+    use super::*;
+    use crate::{
+        mock::{in_order, MockResponse, MockServer},
+        BindingError,
+    };
+
+    struct User {
+        id: i64,
+        tags: Vec<&'static str>,
+    }
+
+    impl BindRow for User {
+        fn bind_row(&self) -> Result<Vec<BindingValue>, BindingError> {
+            Ok(vec![self.id.into(), Json(&self.tags).try_into()?])
+        }
+    }
+
+    impl Insertable for User {
+        const INSERT: &str =
+            "INSERT INTO users (id, tags) SELECT column1, PARSE_JSON(column2) FROM VALUES (?, ?)";
+        const INSERT_VALUES_ROW: Option<&str> = Some("(?, ?)");
+    }
+
+    #[tokio::test]
+    async fn values_rows_are_bound_one_value_at_a_time() {
+        let server = MockServer::start(in_order([MockResponse::new(
+            200,
+            r#"{"message": "ok", "stats": {"numRowsInserted": 2}}"#,
+        )]))
+        .await;
+
+        let users = [
+            User {
+                id: 1,
+                tags: vec!["a"],
+            },
+            User {
+                id: 2,
+                tags: vec![],
+            },
+        ];
+
+        let res = User::insert_many(&server.client(), &users)
+            .await
+            .expect("inserting");
+        assert_eq!(res.stats.rows_inserted, 2);
+
+        let bodies = server.bodies();
+        assert_eq!(bodies.len(), 1);
+        assert_eq!(
+            bodies[0]["statement"],
+            "INSERT INTO users (id, tags) SELECT column1, PARSE_JSON(column2) FROM VALUES (?, ?), (?, ?)"
+        );
+        assert_eq!(
+            bodies[0]["bindings"],
+            serde_json::json!({
+                "1": { "type": "FIXED", "value": "1" },
+                "2": { "type": "TEXT", "value": "[\"a\"]" },
+                "3": { "type": "FIXED", "value": "2" },
+                "4": { "type": "TEXT", "value": "[]" },
+            })
+        );
+    }
+}
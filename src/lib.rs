@@ -1,25 +1,43 @@
 use data_manipulation::DataManipulationResult;
-use std::{collections::HashMap, marker::PhantomData};
+use snowsql_deserialize::RowsSeed;
+use std::{cell::RefCell, collections::HashMap, marker::PhantomData, sync::Arc};
 
+mod backoff;
+mod bind_rows;
+mod cancel;
 mod client;
 mod error;
+mod insertable;
+mod mergeable;
+#[cfg(test)]
+mod mock;
+mod named_bindings;
 mod partitions;
 mod selectable;
+mod statement;
 
 pub mod data_manipulation;
 pub mod jwt;
 
 pub use {
+    backoff::{Backoff, RetryPolicy},
+    bind_rows::BindRow,
     client::Client,
-    error::{CredentialsError, Error},
-    partitions::Partitions,
+    error::{BindingError, CredentialsError, Error, SnowflakeError},
+    insertable::Insertable,
+    mergeable::Mergeable,
+    partitions::{Partition, PartitionStream, Partitions, PartitionsState, RowStream},
     selectable::*,
     serde,
-    snowsql_derive::{FromRow, Selectable},
+    snowsql_derive::{FromRow, Insertable, Mergeable, Selectable},
     snowsql_deserialize::{
-        BindingKind, BindingValue, Error as DeserializeError, FromRow, FromRowResult, FromValue,
-        RawRow, Result as DeserializeResult, Row, RowAccess,
+        accepts_json, from_json, BindingKind, BindingType, BindingValue, ColumnIndex, ColumnSchema,
+        DynamicRow, Error as DeserializeError, FromRow, FromRowResult, FromValue, Json,
+        NamedRowAccess, RawRow, Result as DeserializeResult, Row, RowAccess, RowType, Schema,
+        SchemaIssue, SchemaMismatch, SnowflakeType, Timestamp, TimestampLtz, Value,
     },
+    statement::{StatementHandle, StatementStatus},
+    tokio_util::sync::CancellationToken,
 };
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -27,6 +45,8 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub struct PrivateKey(pub String);
 pub struct PublicKey(pub String);
 
+const DEFAULT_MAX_PAYLOAD_BYTES: usize = 8 * 1024 * 1024;
+
 pub fn sql<R>(statement: impl Into<String>) -> QueryBuilder<R>
 where
     R: FromRow,
@@ -34,73 +54,146 @@ where
     QueryBuilder::new(statement)
 }
 
-trait ResponseOk {
+trait ResponseOk: Sized {
+    /// Deserializes the body with `seed`.
+    async fn snowflake_response_seed<S, T>(self, seed: S) -> Result<T>
+    where
+        S: for<'de> serde::de::DeserializeSeed<'de, Value = T>;
+
     async fn snowflake_response<T>(self) -> Result<T>
     where
-        T: serde::de::DeserializeOwned;
+        T: serde::de::DeserializeOwned,
+    {
+        self.snowflake_response_seed(PhantomData::<T>).await
+    }
+
+    /// Like [`Self::snowflake_response`] for a result,
+    /// checking its columns against `schema`, if any, before parsing rows.
+    async fn snowflake_rows<R>(self, schema: Option<&Schema>) -> Result<Response<Row<R>>>
+    where
+        R: FromRow,
+    {
+        let mismatch = RefCell::new(None);
+
+        let res = self
+            .snowflake_response_seed(ResponseSeed {
+                schema,
+                mismatch: &mismatch,
+                _marker: PhantomData,
+            })
+            .await;
+
+        match mismatch.into_inner() {
+            Some(mismatch) => Err(Error::Schema(mismatch)),
+            None => res,
+        }
+    }
 }
 
 impl ResponseOk for reqwest::Response {
-    async fn snowflake_response<T>(self) -> Result<T>
+    async fn snowflake_response_seed<S, T>(self, seed: S) -> Result<T>
     where
-        T: serde::de::DeserializeOwned,
+        S: for<'de> serde::de::DeserializeSeed<'de, Value = T>,
     {
         let status = self.status();
         let bs = self.bytes().await?;
 
-        if !status.is_success() {
-            let body = String::from_utf8_lossy(&bs).into();
-            return Err(Error::NokResponse { status, body });
-        }
+        deserialize_response_seed(status, &bs, seed)
+    }
+}
+
+impl ResponseOk for client::ResponseBody {
+    async fn snowflake_response_seed<S, T>(self, seed: S) -> Result<T>
+    where
+        S: for<'de> serde::de::DeserializeSeed<'de, Value = T>,
+    {
+        deserialize_response_seed(self.status, &self.body, seed)
+    }
+}
+
+fn deserialize_response_seed<'de, S>(
+    status: reqwest::StatusCode,
+    bs: &'de [u8],
+    seed: S,
+) -> Result<S::Value>
+where
+    S: serde::de::DeserializeSeed<'de>,
+{
+    if !status.is_success() {
+        return Err(nok_error(status, bs));
+    }
+
+    let mut des = serde_json::Deserializer::from_slice(bs);
+
+    match seed.deserialize(&mut des).and_then(|value| {
+        des.end()?;
+        Ok(value)
+    }) {
+        Ok(deserialized) => Ok(deserialized),
+        Err(err) => {
+            let lines_to_skip = err.line().max(1) - 1;
+            let chars_to_skip = err.column().max(100) - 100;
+
+            let mut iter = bs.iter();
+
+            let mut line_counter = 0;
+            let mut b_counter = 0;
+
+            let extract_bs = (&mut iter)
+                .skip_while(|&&b| {
+                    if b == b'\n' {
+                        line_counter += 1;
+                    }
 
-        match serde_json::from_slice::<T>(&bs) {
-            Ok(deserialized) => Ok(deserialized),
-            Err(err) => {
-                let lines_to_skip = err.line().max(1) - 1;
-                let chars_to_skip = err.column().max(100) - 100;
-
-                let mut iter = bs.iter();
-
-                let mut line_counter = 0;
-                let mut b_counter = 0;
-
-                let extract_bs = (&mut iter)
-                    .skip_while(|&&b| {
-                        if b == b'\n' {
-                            line_counter += 1;
-                        }
-
-                        line_counter < lines_to_skip
-                    })
-                    .skip_while(|_| {
-                        b_counter += 1;
-                        b_counter < chars_to_skip
-                    })
-                    .copied()
-                    .take(200)
-                    .collect::<Vec<u8>>();
-
-                Err(Error::DeserializeSnowflakeResponse {
-                    err,
-                    body: String::from_utf8_lossy(&extract_bs).into(),
+                    line_counter < lines_to_skip
                 })
-            }
+                .skip_while(|_| {
+                    b_counter += 1;
+                    b_counter < chars_to_skip
+                })
+                .copied()
+                .take(200)
+                .collect::<Vec<u8>>();
+
+            Err(Error::DeserializeSnowflakeResponse {
+                err,
+                body: String::from_utf8_lossy(&extract_bs).into(),
+            })
         }
     }
 }
 
+fn nok_error(status: reqwest::StatusCode, bs: &[u8]) -> Error {
+    if let Ok(mut err) = serde_json::from_slice::<SnowflakeError>(bs) {
+        err.status = status;
+        return Error::Snowflake(err);
+    }
+
+    let body = String::from_utf8_lossy(bs).into();
+    Error::NokResponse { status, body }
+}
+
 #[derive(Clone, Debug)]
 pub struct QueryBuilder<R> {
     pub statement: String,
     timeout: Option<u32>,
     role: Option<String>,
-    bindings: HashMap<String, Binding>,
+    bindings: Vec<BindingValue>,
+    named_bindings: HashMap<String, BindingValue>,
+    /// Rows bound with [`Self::bind_rows`], `None` when not used.
+    rows: Option<Vec<Vec<BindingValue>>>,
+    /// The first value that could not be bound, reported when the query is built.
+    binding_error: Option<BindingError>,
+    max_payload_bytes: usize,
     order_by: Option<String>,
     offset: Option<usize>,
     limit: Option<usize>,
 
     parameters: Option<StatementParameters>,
 
+    cancellation: cancel::Cancellation,
+    schema: Option<Schema>,
+
     _marker: PhantomData<R>,
 }
 
@@ -113,11 +206,17 @@ where
             statement: query.into(),
             timeout: None,
             role: None,
-            bindings: HashMap::default(),
+            bindings: Vec::new(),
+            named_bindings: HashMap::default(),
+            rows: None,
+            binding_error: None,
+            max_payload_bytes: DEFAULT_MAX_PAYLOAD_BYTES,
             order_by: None,
             offset: None,
             limit: None,
             parameters: None,
+            cancellation: cancel::Cancellation::default(),
+            schema: None,
             _marker: PhantomData,
         }
     }
@@ -127,9 +226,8 @@ where
         statement.push_str(&self.statement);
 
         if let Some(order_by) = self.order_by.as_deref() {
-            statement.push_str(" ORDER BY '");
+            statement.push_str(" ORDER BY ");
             statement.push_str(order_by);
-            statement.push('\'');
         }
 
         if let Some(limit) = self.limit {
@@ -143,53 +241,184 @@ where
         statement
     }
 
-    fn build_query(self) -> SnowflakeQuery {
-        SnowflakeQuery {
-            statement: self.build_statement(),
+    fn build_query(self) -> Result<SnowflakeQuery> {
+        if let Some(err) = self.binding_error {
+            return Err(err.into());
+        }
+
+        let mut statement = self.build_statement();
+
+        let binding_styles = [
+            !self.bindings.is_empty(),
+            !self.named_bindings.is_empty(),
+            self.rows.is_some(),
+        ];
+
+        if 1 < binding_styles.into_iter().filter(|&used| used).count() {
+            return Err(BindingError::Mixed.into());
+        }
+
+        let mut bindings = positional_bindings(self.bindings);
+
+        if let Some(rows) = &self.rows {
+            bindings = bind_rows::column_bindings(rows)?;
+        }
+
+        if !self.named_bindings.is_empty() {
+            let (rewritten, values) = named_bindings::resolve(&statement, &self.named_bindings)?;
+
+            statement = rewritten;
+            bindings = positional_bindings(values);
+        }
+
+        Ok(SnowflakeQuery {
+            statement,
             timeout: self.timeout,
             role: self.role,
-            bindings: self.bindings,
+            bindings,
             parameters: self.parameters,
+        })
+    }
+
+    /// Splits bound rows into queries that fit in a request,
+    /// see [`Self::with_max_payload_bytes`].
+    ///
+    /// Returns no query when [`Self::bind_rows`] was given no rows.
+    fn build_queries(mut self) -> Result<Vec<SnowflakeQuery>> {
+        let Some(rows) = self.rows.take() else {
+            return Ok(vec![self.build_query()?]);
+        };
+
+        if !self.bindings.is_empty() || !self.named_bindings.is_empty() {
+            return Err(BindingError::Mixed.into());
         }
+
+        let max_bytes = self.max_payload_bytes.saturating_sub(self.statement.len());
+        let query = self.build_query()?;
+
+        bind_rows::chunk(rows, max_bytes, 0)
+            .into_iter()
+            .map(|rows| {
+                Ok(SnowflakeQuery {
+                    bindings: bind_rows::column_bindings(&rows)?,
+                    ..query.clone()
+                })
+            })
+            .collect()
     }
 
+    /// The statement as sent to Snowflake, with `:name` placeholders rewritten to `?`.
     pub fn as_statement(&self) -> String {
-        self.build_statement()
+        let statement = self.build_statement();
+
+        if self.named_bindings.is_empty() {
+            statement
+        } else {
+            named_bindings::rewrite(&statement).0
+        }
     }
 
     pub async fn text(self, c: &Client) -> Result<String> {
-        Ok(c.post()?
-            .json(&self.build_query())
-            .send()
-            .await?
-            .text()
-            .await?)
+        let cancellation = self.cancellation.clone();
+
+        let res = c.execute(&self.build_query()?, &cancellation).await?;
+
+        Ok(String::from_utf8_lossy(&res.body).into_owned())
     }
 
+    /// Executes the statement and waits for it to finish,
+    /// polling Snowflake if the statement runs asynchronously.
     pub async fn query(self, c: &Client) -> Result<Response<Row<R>>> {
-        let qry = self.build_query();
+        let cancellation = self.cancellation.clone();
+        let schema = self.schema;
 
-        let response = c
-            .post()?
-            .json(&qry)
-            .send()
-            .await?
-            .snowflake_response::<Response<Row<R>>>()
-            .await?;
+        let res = c.execute(&self.build_query()?, &cancellation).await?;
+
+        res.snowflake_rows(schema.as_ref()).await
+    }
 
-        Ok(response)
+    /// Like [`Self::query`], decoding each row into a [`DynamicRow`] using the result's column metadata.
+    pub async fn query_dynamic(self, c: &Client) -> Result<Response<Row<DynamicRow>>> {
+        self.cast::<DynamicRow>().query(c).await
+    }
+
+    fn cast<T>(self) -> QueryBuilder<T> {
+        QueryBuilder {
+            statement: self.statement,
+            timeout: self.timeout,
+            role: self.role,
+            bindings: self.bindings,
+            named_bindings: self.named_bindings,
+            rows: self.rows,
+            binding_error: self.binding_error,
+            max_payload_bytes: self.max_payload_bytes,
+            order_by: self.order_by,
+            offset: self.offset,
+            limit: self.limit,
+            parameters: self.parameters,
+            cancellation: self.cancellation,
+            schema: self.schema,
+            _marker: PhantomData,
+        }
     }
 
     /// Use with `delete`, `insert`, `update` row(s).
+    ///
+    /// Rows bound with [`Self::bind_rows`] are sent in as many requests as needed,
+    /// and the changes of all requests are summed.
+    /// Without any row, nothing is sent and no changes are returned.
     pub async fn manipulate(self, c: &Client) -> Result<DataManipulationResult> {
+        let cancellation = self.cancellation.clone();
+
+        let mut total = DataManipulationResult::default();
+
+        for qry in self.build_queries()? {
+            let res = c
+                .execute(&qry, &cancellation)
+                .await?
+                .snowflake_response::<DataManipulationResult>()
+                .await?;
+
+            total = total.merge(res);
+        }
+
+        Ok(total)
+    }
+
+    /// Executes multiple statements, set up with [`Self::multi`], in one request.
+    ///
+    /// Returns the handles of the child statements in order,
+    /// fetch their results with [`StatementHandle::result`] or [`StatementHandle::manipulation_result`].
+    pub async fn statements(self, c: &Client) -> Result<Vec<StatementHandle>> {
+        let cancellation = self.cancellation.clone();
+
         let res = c
-            .post()?
-            .json(&self.build_query())
-            .send()
+            .execute(&self.build_query()?, &cancellation)
             .await?
-            .snowflake_response::<DataManipulationResult>()
+            .snowflake_response::<statement::MultiStatementResponse>()
             .await?;
-        Ok(res)
+
+        Ok(res
+            .statement_handles
+            .into_iter()
+            .map(StatementHandle::from)
+            .collect())
+    }
+
+    /// Submits the statement without waiting for it to finish.
+    ///
+    /// Use the returned handle to check on the statement and fetch its result.
+    pub async fn submit(self, c: &Client) -> Result<StatementHandle> {
+        let query = self.build_query()?;
+        let request_id = uuid::Uuid::new_v4();
+
+        let status = c
+            .send(|retry| Ok(c.post_async(&request_id, retry)?.json(&query)))
+            .await?
+            .snowflake_response::<statement::QueryStatus>()
+            .await?;
+
+        Ok(StatementHandle::new(status.statement_handle))
     }
 
     pub fn sql(mut self, s: impl AsRef<str>) -> Self {
@@ -198,6 +427,9 @@ where
         self
     }
 
+    /// Orders the result by the column `field`, written into the statement as an identifier.
+    ///
+    /// Quote names Snowflake should not upper-case, e.g. `r#""displayName""#`.
     pub fn order_by(mut self, field: impl Into<String>) -> Self {
         self.order_by = Some(field.into());
         self
@@ -223,6 +455,23 @@ where
         self
     }
 
+    /// Cancels the statement on Snowflake if the future returned by
+    /// [`Self::query`], [`Self::manipulate`] or [`Self::text`] is dropped before it finishes.
+    ///
+    /// The statement can only be cancelled once Snowflake has answered its submission
+    /// with a statement handle: a future dropped before then leaves it running.
+    pub fn cancel_on_drop(mut self) -> Self {
+        self.cancellation.on_drop = true;
+        self
+    }
+
+    /// Cancels the statement on Snowflake when `token` is cancelled,
+    /// making the query return [`Error::Cancelled`], even if the cancel request fails.
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation.token = Some(token);
+        self
+    }
+
     /// (Optional) Specifies the maximum size of each set (or chunk) of query results to download (in MB).
     /// For details, see
     /// [CLIENT_RESULT_CHUNK_SIZE](https://docs.snowflake.com/sql-reference/parameters.html#label-client-result-chunk-size).
@@ -251,19 +500,101 @@ where
         self
     }
 
-    pub fn add_binding<T: Into<BindingValue>>(mut self, value: T) -> Self {
-        let value: BindingValue = value.into();
+    /// (Optional) Allows the statement to contain `count` statements separated by `;`,
+    /// with 0 meaning any number. For details, see
+    /// [MULTI_STATEMENT_COUNT](https://docs.snowflake.com/sql-reference/parameters#multi-statement-count).
+    ///
+    /// Execute with [`Self::statements`].
+    pub fn multi(mut self, count: usize) -> Self {
+        self.parameters
+            .get_or_insert_with(StatementParameters::default)
+            .statement_count = Some(count);
+        self
+    }
 
-        let binding = Binding {
-            kind: value.kind(),
-            value: value.to_string(),
-        };
+    /// Binds `value` to the next `?` placeholder.
+    ///
+    /// A value failing to convert, e.g. a [`Json`] that does not serialize,
+    /// fails the query with [`BindingError`].
+    pub fn add_binding<T>(mut self, value: T) -> Self
+    where
+        T: TryInto<BindingValue>,
+        BindingError: From<T::Error>,
+    {
+        match value.try_into() {
+            Ok(value) => self.bindings.push(value),
+            Err(err) => self.set_binding_error(err.into()),
+        }
+        self
+    }
+
+    /// Checks the result's columns against `schema` before parsing any row,
+    /// failing with [`Error::Schema`] on a mismatch.
+    ///
+    /// Use [`Selectable::SCHEMA`] for derived structs.
+    /// Results fetched later are checked with [`Client::fetch_validated`] and [`Partitions::resume_validated`].
+    pub fn with_schema_validation(mut self, schema: Schema) -> Self {
+        self.schema = Some(schema);
+        self
+    }
 
-        self.bindings
-            .insert((self.bindings.len() + 1).to_string(), binding);
+    /// Binds each row's values to the `?` placeholders as arrays, inserting all rows in one statement.
+    ///
+    /// Cannot be combined with other bindings.
+    /// With [`Self::manipulate`], rows are split over several requests
+    /// when they exceed [`Self::with_max_payload_bytes`],
+    /// the other methods send all rows in a single request.
+    pub fn bind_rows<I>(mut self, rows: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: BindRow,
+    {
+        let mut bound = Vec::new();
+
+        for row in rows {
+            match row.bind_row() {
+                Ok(row) => bound.push(row),
+                Err(err) => self.set_binding_error(err),
+            }
+        }
 
+        self.rows.get_or_insert_with(Vec::new).extend(bound);
+        self
+    }
+
+    /// Approximate size limit of a request with rows bound with [`Self::bind_rows`],
+    /// only used by [`Self::manipulate`].
+    ///
+    /// Defaults to 8 MB.
+    pub fn with_max_payload_bytes(mut self, bytes: usize) -> Self {
+        self.max_payload_bytes = bytes;
+        self
+    }
+
+    /// Binds `value` to the `:name` placeholders in the statement,
+    /// which may appear more than once.
+    ///
+    /// Cannot be combined with [`Self::add_binding`].
+    /// Placeholders without a value, or values without a placeholder,
+    /// fail the query with [`BindingError`].
+    pub fn bind<T>(mut self, name: impl Into<String>, value: T) -> Self
+    where
+        T: TryInto<BindingValue>,
+        BindingError: From<T::Error>,
+    {
+        match value.try_into() {
+            Ok(value) => {
+                self.named_bindings.insert(name.into(), value);
+            }
+            Err(err) => self.set_binding_error(err.into()),
+        }
         self
     }
+
+    /// Keeps the first error, so that it is reported when the query is built.
+    fn set_binding_error(&mut self, err: BindingError) {
+        self.binding_error.get_or_insert(err);
+    }
 }
 
 #[derive(Default, Clone, serde::Serialize, Debug)]
@@ -273,6 +604,12 @@ pub struct StatementParameters {
 
     #[serde(rename = "rows_per_resultset")]
     pub rows_per_set: Option<usize>,
+
+    #[serde(
+        rename = "multi_statement_count",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub statement_count: Option<usize>,
 }
 
 #[derive(Clone, serde::Serialize, Debug)]
@@ -292,7 +629,41 @@ pub struct SnowflakeQuery {
 pub struct Binding {
     #[serde(rename = "type")]
     kind: BindingKind,
-    value: String,
+    value: BindingData,
+}
+
+#[derive(Clone, serde::Serialize, Debug)]
+#[serde(untagged)]
+enum BindingData {
+    Value(Option<String>),
+    Array(Vec<Option<String>>),
+}
+
+impl Binding {
+    fn array(kind: BindingKind, values: Vec<Option<String>>) -> Self {
+        Self {
+            kind,
+            value: BindingData::Array(values),
+        }
+    }
+}
+
+impl From<BindingValue> for Binding {
+    fn from(value: BindingValue) -> Self {
+        Self {
+            kind: value.kind(),
+            value: BindingData::Value(value.value()),
+        }
+    }
+}
+
+/// Bindings numbered from 1, for the `?` placeholders in order.
+fn positional_bindings(values: impl IntoIterator<Item = BindingValue>) -> HashMap<String, Binding> {
+    values
+        .into_iter()
+        .enumerate()
+        .map(|(i, value)| ((i + 1).to_string(), Binding::from(value)))
+        .collect()
 }
 
 #[derive(serde::Deserialize, Debug)]
@@ -302,15 +673,142 @@ pub struct PartitionInfo {
     pub uncompressed_size: usize,
 }
 
-#[derive(serde::Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug)]
 pub struct Response<R> {
     pub data: Vec<R>,
 
-    #[serde(flatten)]
     pub info: ResponseInfo,
 }
 
+impl<'de, R> serde::Deserialize<'de> for Response<Row<R>>
+where
+    R: FromRow,
+{
+    fn deserialize<D>(des: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::DeserializeSeed;
+
+        ResponseSeed {
+            schema: None,
+            mismatch: &RefCell::new(None),
+            _marker: PhantomData,
+        }
+        .deserialize(des)
+    }
+}
+
+/// Deserializes `data` with the columns of `resultSetMetaData`, see [`RowsSeed`].
+///
+/// When `data` comes before the metadata, its JSON text is kept, rather than parsed values,
+/// until the metadata is known.
+///
+/// With a `schema`, the columns are checked before any row is parsed,
+/// a mismatch is put in `mismatch` and fails deserialization.
+struct ResponseSeed<'s, R> {
+    schema: Option<&'s Schema>,
+    mismatch: &'s RefCell<Option<SchemaMismatch>>,
+    _marker: PhantomData<R>,
+}
+
+impl<'de, R> serde::de::DeserializeSeed<'de> for ResponseSeed<'_, R>
+where
+    R: FromRow,
+{
+    type Value = Response<Row<R>>;
+
+    fn deserialize<D>(self, des: D) -> std::result::Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        des.deserialize_map(self)
+    }
+}
+
+impl<R> ResponseSeed<'_, R> {
+    fn validate<E>(&self, columns: &[RowType]) -> std::result::Result<(), E>
+    where
+        E: serde::de::Error,
+    {
+        let Some(schema) = self.schema else {
+            return Ok(());
+        };
+
+        schema.validate(columns).map_err(|mismatch| {
+            let err = E::custom(&mismatch);
+            *self.mismatch.borrow_mut() = Some(mismatch);
+            err
+        })
+    }
+}
+
+impl<'de, R> serde::de::Visitor<'de> for ResponseSeed<'_, R>
+where
+    R: FromRow,
+{
+    type Value = Response<Row<R>>;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a snowflake response")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> std::result::Result<Self::Value, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        use serde::{
+            de::{DeserializeSeed, Error as _},
+            Deserialize,
+        };
+
+        let mut info = serde_json::Map::new();
+        let mut columns = None::<Arc<[RowType]>>;
+        let mut data = None::<Vec<Row<R>>>;
+        let mut raw_data = None::<Box<serde_json::value::RawValue>>;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match (key.as_str(), &columns) {
+                ("data", Some(columns)) => {
+                    data = Some(map.next_value_seed(RowsSeed::new(Some(columns)))?)
+                }
+                ("data", None) => raw_data = Some(map.next_value()?),
+                ("resultSetMetaData", _) => {
+                    let meta = map.next_value::<serde_json::Value>()?;
+
+                    if let Some(row_type) = meta.get("rowType") {
+                        let row_type = Arc::deserialize(row_type).map_err(A::Error::custom)?;
+                        self.validate(&row_type)?;
+                        columns = Some(row_type);
+                    }
+
+                    info.insert(key, meta);
+                }
+                _ => {
+                    info.insert(key, map.next_value()?);
+                }
+            }
+        }
+
+        if columns.is_none() {
+            self.validate(&[])?;
+        }
+
+        let data = match (data, raw_data) {
+            (Some(data), _) => data,
+            (None, Some(raw)) => RowsSeed::new(columns.as_ref())
+                .deserialize(&mut serde_json::Deserializer::from_str(raw.get()))
+                .map_err(A::Error::custom)?,
+            (None, None) => return Err(A::Error::missing_field("data")),
+        };
+
+        let info =
+            ResponseInfo::deserialize(serde_json::Value::Object(info)).map_err(A::Error::custom)?;
+
+        Ok(Response { data, info })
+    }
+}
+
 #[derive(serde::Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ResponseInfo {
@@ -322,6 +820,9 @@ pub struct ResponseInfo {
     pub sql_state: String,
     pub message: String,
     pub statement_handle: String,
+    /// Handles of the child statements, set when multiple statements were executed.
+    #[serde(default)]
+    pub statement_handles: Vec<String>,
     //pub created_on: u64,
 }
 
@@ -332,6 +833,14 @@ where
     pub fn partitions(self) -> Partitions<R> {
         Partitions::from_response(self)
     }
+
+    /// Streams the rows of all partitions, downloading later partitions as the stream is consumed.
+    pub fn rows(self, c: &Client) -> RowStream<R>
+    where
+        R: Send + 'static,
+    {
+        self.partitions().stream(c).rows()
+    }
 }
 
 #[derive(serde::Deserialize, Debug)]
@@ -343,19 +852,138 @@ pub struct MetaData {
     partition_info: Vec<PartitionInfo>,
 }
 
-#[derive(serde::Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
-pub struct RowType {
-    pub name: String,
-    pub database: String,
-    pub schema: String,
-    pub table: String,
-    pub precision: Option<u32>,
-    pub byte_length: Option<usize>,
-    #[serde(rename = "type")]
-    pub data_type: String,
-    pub scale: Option<i32>,
-    pub nullable: bool,
-    //pub collation: ???,
-    //pub length: ???,
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::mock::{in_order, MockResponse, MockServer};
+
+    const ID_SCHEMA: Schema = Schema {
+        columns: &[ColumnSchema {
+            name: "ID",
+            nullable: false,
+            accepts: |col| matches!(col.snowflake_type, SnowflakeType::Fixed { scale: 0, .. }),
+        }],
+        by_name: false,
+    };
+
+    /// A result with an `ID` column of `data_type`, its rows before the metadata.
+    fn result(data_type: &str) -> MockResponse {
+        MockResponse::new(
+            200,
+            format!(
+                r#"{{
+  "data": [["not a number"]],
+  "resultSetMetaData": {{
+    "numRows": 1,
+    "format": "jsonv2",
+    "partitionInfo": [{{ "rowCount": 1, "uncompressedSize": 16 }}],
+    "rowType": [{{ "name": "ID", "database": "DB", "schema": "PUBLIC", "table": "T", "byteLength": null, "type": "{data_type}", "scale": 0, "precision": 38, "length": null, "nullable": false }}]
+  }},
+  "code": "090001",
+  "statementStatusUrl": "/api/v2/statements/h1",
+  "requestId": "r1",
+  "sqlState": "00000",
+  "statementHandle": "h1",
+  "message": "Statement executed successfully."
+}}"#
+            ),
+        )
+    }
+
+    #[tokio::test]
+    async fn query_validates_columns_before_rows() {
+        let server = MockServer::start(in_order([result("text")])).await;
+
+        let err = sql::<RawRow>("SELECT id FROM t")
+            .with_schema_validation(ID_SCHEMA)
+            .query(&server.client())
+            .await
+            .expect_err("validating columns");
+
+        let Error::Schema(mismatch) = err else {
+            panic!("expected a schema mismatch, got {err}");
+        };
+        assert!(matches!(
+            mismatch.issues[..],
+            [SchemaIssue::Type { column: "ID", .. }]
+        ));
+    }
+
+    #[tokio::test]
+    async fn fetch_validates_columns() {
+        let server = MockServer::start(in_order([result("text"), result("fixed")])).await;
+        let c = server.client();
+
+        let err = c
+            .fetch_validated::<RawRow>("h1", &ID_SCHEMA)
+            .await
+            .expect_err("validating columns");
+        assert!(matches!(err, Error::Schema(_)), "{err}");
+
+        let res = c
+            .fetch_validated::<RawRow>("h1", &ID_SCHEMA)
+            .await
+            .expect("fetching");
+        assert_eq!(res.data[0].0 .0, [Some("not a number".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn invalid_bindings_fail_before_sending() {
+        let server = MockServer::start(in_order([])).await;
+        let c = server.client();
+        let invalid = || Json(HashMap::from([((1, 2), "a")]));
+
+        let queries = [
+            sql::<RawRow>("SELECT ?").add_binding(invalid()),
+            sql::<RawRow>("SELECT :a").bind("a", invalid()),
+            sql::<RawRow>("INSERT INTO t (a) VALUES (?)").bind_rows([(invalid(),)]),
+        ];
+
+        for qry in queries {
+            let err = qry.manipulate(&c).await.expect_err("binding invalid value");
+            assert!(
+                matches!(err, Error::Binding(BindingError::Json(_))),
+                "{err}"
+            );
+        }
+
+        assert!(server.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn manipulate_without_rows_sends_nothing() {
+        let server = MockServer::start(in_order([])).await;
+
+        let res = sql::<RawRow>("INSERT INTO t (a) VALUES (?)")
+            .bind_rows(Vec::<(i64,)>::new())
+            .manipulate(&server.client())
+            .await
+            .expect("manipulating");
+
+        assert_eq!(res.stats.rows_inserted, 0);
+        assert!(server.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn manipulate_sums_chunked_requests() {
+        let inserted = |count| {
+            MockResponse::new(
+                200,
+                format!(r#"{{"message": "ok", "stats": {{"numRowsInserted": {count}}}}}"#),
+            )
+        };
+        let server = MockServer::start(in_order([inserted(2), inserted(1)])).await;
+
+        let statement = "INSERT INTO t (a) VALUES (?)";
+        let res = sql::<RawRow>(statement)
+            .bind_rows([("aaaa",), ("bbbb",), ("cccc",)])
+            .with_max_payload_bytes(statement.len() + 20)
+            .manipulate(&server.client())
+            .await
+            .expect("manipulating");
+
+        assert_eq!(res.stats.rows_inserted, 3);
+        assert_eq!(server.requests().len(), 2);
+    }
 }
@@ -0,0 +1,161 @@
+use snowsql_deserialize::{BindingValue, RawRow};
+
+use crate::{bind_rows::values_chunks, data_manipulation::DataManipulationResult, BindingError};
+
+#[allow(async_fn_in_trait)]
+pub trait Mergeable
+where
+    Self: Sized,
+{
+    const MERGE_TABLE: &str;
+    /// Columns written by the merge, leaving out fields marked `skip_insert`.
+    const MERGE_COLUMNS: &[&str];
+    /// Columns marked `key`, matched on to decide between update and insert.
+    const MERGE_KEYS: &[&str];
+    /// Columns marked `json`, parsed with `PARSE_JSON`.
+    const MERGE_JSON: &[&str] = &[];
+
+    /// Values of [`Self::MERGE_COLUMNS`], in order.
+    fn merge_values(&self) -> Result<Vec<BindingValue>, BindingError>;
+
+    /// `MERGE INTO table USING (...) ON key = ... WHEN MATCHED THEN UPDATE ... WHEN NOT MATCHED THEN INSERT ...`
+    /// with placeholders for `row_count` rows.
+    fn merge_statement(row_count: usize) -> String {
+        let source = if row_count == 1 {
+            let cols = Self::MERGE_COLUMNS
+                .iter()
+                .map(|col| format!("{} AS {col}", Self::parse_json("?", col)))
+                .collect::<Vec<_>>();
+
+            format!("SELECT {}", cols.join(", "))
+        } else {
+            let cols = Self::MERGE_COLUMNS
+                .iter()
+                .enumerate()
+                .map(|(i, col)| {
+                    let value = format!("column{}", i + 1);
+                    format!("{} AS {col}", Self::parse_json(&value, col))
+                })
+                .collect::<Vec<_>>();
+            let row = format!("({})", vec!["?"; Self::MERGE_COLUMNS.len()].join(", "));
+
+            format!(
+                "SELECT {} FROM VALUES {}",
+                cols.join(", "),
+                vec![row; row_count].join(", ")
+            )
+        };
+
+        let on = Self::MERGE_KEYS
+            .iter()
+            .map(|col| format!("target.{col} = source.{col}"))
+            .collect::<Vec<_>>();
+
+        let updates = Self::MERGE_COLUMNS
+            .iter()
+            .filter(|col| !Self::MERGE_KEYS.contains(col))
+            .map(|col| format!("target.{col} = source.{col}"))
+            .collect::<Vec<_>>();
+
+        let when_matched = if updates.is_empty() {
+            String::new()
+        } else {
+            format!(" WHEN MATCHED THEN UPDATE SET {}", updates.join(", "))
+        };
+
+        let source_cols = Self::MERGE_COLUMNS
+            .iter()
+            .map(|col| format!("source.{col}"))
+            .collect::<Vec<_>>();
+
+        format!(
+            "MERGE INTO {} AS target USING ({source}) AS source ON {}{when_matched} WHEN NOT MATCHED THEN INSERT ({}) VALUES ({})",
+            Self::MERGE_TABLE,
+            on.join(" AND "),
+            Self::MERGE_COLUMNS.join(", "),
+            source_cols.join(", "),
+        )
+    }
+
+    /// Wraps `value` in `PARSE_JSON` for JSON columns.
+    fn parse_json(value: &str, column: &str) -> String {
+        if Self::MERGE_JSON.contains(&column) {
+            format!("PARSE_JSON({value})")
+        } else {
+            value.to_owned()
+        }
+    }
+
+    async fn merge(&self, c: &crate::Client) -> crate::Result<DataManipulationResult> {
+        Self::merge_many(c, std::slice::from_ref(self)).await
+    }
+
+    /// Merges all rows, in as many statements as needed to keep each request
+    /// within [`crate::QueryBuilder::with_max_payload_bytes`]'s default.
+    ///
+    /// Keys must be unique among `rows`: a target row matching several rows fails the MERGE,
+    /// and rows matching no target row are all inserted.
+    async fn merge_many(c: &crate::Client, rows: &[Self]) -> crate::Result<DataManipulationResult> {
+        let rows = rows
+            .iter()
+            .map(Self::merge_values)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut total = DataManipulationResult::default();
+
+        let statement_len = Self::merge_statement(0).len();
+
+        for rows in values_chunks(rows, crate::DEFAULT_MAX_PAYLOAD_BYTES, statement_len) {
+            let qry = rows.iter().flatten().fold(
+                crate::sql::<RawRow>(Self::merge_statement(rows.len())),
+                |qry, value| qry.add_binding(value.clone()),
+            );
+
+            total = total.merge(qry.manipulate(c).await?);
+        }
+
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::bind_rows::{binding_overhead, row_size};
+
+    struct Pair(String, String);
+
+    impl Mergeable for Pair {
+        const MERGE_TABLE: &str = "t";
+        const MERGE_COLUMNS: &[&str] = &["a", "b"];
+        const MERGE_KEYS: &[&str] = &["a"];
+
+        fn merge_values(&self) -> Result<Vec<BindingValue>, BindingError> {
+            Ok(vec![self.0.clone().into(), self.1.clone().into()])
+        }
+    }
+
+    #[test]
+    fn chunks_count_statement_growth() {
+        let rows = (0..100)
+            .map(|i| Pair(format!("{i:04}"), "x".repeat(10)).merge_values())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        let max_bytes = 2_000;
+        let chunks = values_chunks(rows, max_bytes, Pair::merge_statement(0).len());
+
+        assert!(1 < chunks.len());
+        assert_eq!(chunks.iter().map(Vec::len).sum::<usize>(), 100);
+
+        for chunk in chunks {
+            let values = chunk
+                .iter()
+                .map(|row| row_size(row) + row.len() * binding_overhead(chunk.len() * row.len()))
+                .sum::<usize>();
+
+            assert!(Pair::merge_statement(chunk.len()).len() + values <= max_bytes);
+        }
+    }
+}
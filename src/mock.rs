@@ -0,0 +1,200 @@
+//! A local HTTP server answering requests with canned responses, for tests.
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+use crate::{Backoff, Client, RetryPolicy};
+
+pub(crate) struct MockResponse {
+    pub status: u16,
+    pub body: String,
+    /// Time to wait before answering.
+    pub delay: Duration,
+    /// Whether the connection is closed before the whole body is sent.
+    pub truncated: bool,
+}
+
+impl MockResponse {
+    pub fn new(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: body.into(),
+            delay: Duration::ZERO,
+            truncated: false,
+        }
+    }
+
+    pub fn delayed(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Announces a longer body than is sent, failing the read of the body.
+    pub fn truncated(mut self) -> Self {
+        self.truncated = true;
+        self
+    }
+}
+
+/// A handler answering requests with `responses` in order, then with `500`.
+pub(crate) fn in_order(
+    responses: impl IntoIterator<Item = MockResponse>,
+) -> impl Fn(&str) -> MockResponse + Send + Sync {
+    let responses = Mutex::new(responses.into_iter().collect::<VecDeque<_>>());
+
+    move |_| {
+        responses
+            .lock()
+            .unwrap()
+            .pop_front()
+            .unwrap_or_else(|| MockResponse::new(500, "no more responses"))
+    }
+}
+
+type Handler = dyn Fn(&str) -> MockResponse + Send + Sync;
+
+/// Answers each request with the response `handler` returns for its path and query.
+///
+/// Connections are served concurrently and closed after one request.
+pub(crate) struct MockServer {
+    url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+struct Request {
+    /// `METHOD path`
+    line: String,
+    body: String,
+}
+
+impl MockServer {
+    pub async fn start(handler: impl Fn(&str) -> MockResponse + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("binding mock server");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handler = Arc::new(handler) as Arc<Handler>;
+
+        let served = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(serve(stream, handler.clone(), served.clone()));
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    /// A client sending its requests to this server, polling and retrying without delay.
+    pub fn client(&self) -> Client {
+        Client::new_with_base_url(format!("{}/api/v2/", self.url))
+            .with_poll_backoff(Backoff {
+                initial: Duration::from_millis(1),
+                max: Duration::from_millis(1),
+                factor: 1,
+            })
+            .with_retry_policy(RetryPolicy::none())
+    }
+
+    /// Like [`Self::client`], making up to three attempts without delay.
+    pub fn retrying_client(&self) -> Client {
+        self.client().with_retry_policy(RetryPolicy {
+            max_attempts: 3,
+            backoff: Backoff {
+                initial: Duration::ZERO,
+                max: Duration::ZERO,
+                factor: 1,
+            },
+            jitter: false,
+            ..RetryPolicy::default()
+        })
+    }
+
+    /// `METHOD path` of each request received, in order.
+    pub fn requests(&self) -> Vec<String> {
+        let requests = self.requests.lock().unwrap();
+        requests.iter().map(|req| req.line.clone()).collect()
+    }
+
+    /// JSON body of each request received, in order.
+    pub fn bodies(&self) -> Vec<serde_json::Value> {
+        let requests = self.requests.lock().unwrap();
+        requests
+            .iter()
+            .map(|req| serde_json::from_str(&req.body).unwrap_or_default())
+            .collect()
+    }
+}
+
+async fn serve(mut stream: TcpStream, handler: Arc<Handler>, requests: Arc<Mutex<Vec<Request>>>) {
+    let mut buf = Vec::new();
+
+    let head_len = loop {
+        let mut chunk = [0; 1024];
+        let n = stream.read(&mut chunk).await.unwrap_or(0);
+
+        if n == 0 {
+            return;
+        }
+
+        buf.extend_from_slice(&chunk[..n]);
+
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buf[..head_len]).into_owned();
+
+    let content_length = head
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+
+    while buf.len() < head_len + content_length {
+        let mut chunk = [0; 1024];
+        match stream.read(&mut chunk).await {
+            Ok(0) | Err(_) => return,
+            Ok(n) => buf.extend_from_slice(&chunk[..n]),
+        }
+    }
+
+    let mut request_line = head.lines().next().unwrap_or_default().split(' ');
+    let method = request_line.next().unwrap_or_default();
+    let path = request_line.next().unwrap_or_default();
+
+    requests.lock().unwrap().push(Request {
+        line: format!("{method} {path}"),
+        body: String::from_utf8_lossy(&buf[head_len..]).into_owned(),
+    });
+
+    let res = handler(path);
+    tokio::time::sleep(res.delay).await;
+
+    let reason = reqwest::StatusCode::from_u16(res.status)
+        .ok()
+        .and_then(|status| status.canonical_reason())
+        .unwrap_or("");
+
+    let response = format!(
+        "HTTP/1.1 {} {reason}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+        res.status,
+        res.body.len() + usize::from(res.truncated),
+        res.body
+    );
+
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+
+use snowsql_deserialize::BindingValue;
+
+use crate::BindingError;
+
+/// Rewrites `:name` placeholders into positional `?` placeholders,
+/// returning the values in the order the placeholders appear.
+///
+/// Placeholders inside string literals, quoted identifiers and comments are left alone,
+/// as are `::` casts and `col:field` paths.
+pub(crate) fn resolve(
+    statement: &str,
+    values: &HashMap<String, BindingValue>,
+) -> Result<(String, Vec<BindingValue>), BindingError> {
+    let (rewritten, names) = rewrite(statement);
+
+    let mut positional = Vec::with_capacity(names.len());
+
+    for name in &names {
+        let value = values
+            .get(*name)
+            .ok_or_else(|| BindingError::MissingValue((*name).into()))?;
+
+        positional.push(value.clone());
+    }
+
+    let mut unused = values
+        .keys()
+        .filter(|name| !names.contains(&name.as_str()))
+        .collect::<Vec<_>>();
+    unused.sort();
+
+    if let Some(name) = unused.first() {
+        return Err(BindingError::UnusedValue((*name).clone()));
+    }
+
+    Ok((rewritten, positional))
+}
+
+pub(crate) fn rewrite(statement: &str) -> (String, Vec<&str>) {
+    let bs = statement.as_bytes();
+
+    let mut rewritten = String::with_capacity(statement.len());
+    let mut names = Vec::new();
+
+    // start of the text not yet copied to `rewritten`
+    let mut copied = 0;
+    let mut i = 0;
+
+    while i < bs.len() {
+        match bs[i] {
+            b'\'' => i = skip_quoted(bs, i, b'\''),
+            b'"' => i = skip_quoted(bs, i, b'"'),
+            b'$' if bs.get(i + 1) == Some(&b'$') => i = skip_until(bs, i + 2, b"$$"),
+            b'-' if bs.get(i + 1) == Some(&b'-') => i = skip_until(bs, i + 2, b"\n"),
+            b'/' if bs.get(i + 1) == Some(&b'/') => i = skip_until(bs, i + 2, b"\n"),
+            b'/' if bs.get(i + 1) == Some(&b'*') => i = skip_until(bs, i + 2, b"*/"),
+            b':' if bs.get(i + 1) == Some(&b':') => i += 2,
+            // `v:field` is a path into a semi-structured value
+            b':' if 0 < i && is_path_base(bs[i - 1]) => i += 1,
+            b':' if bs.get(i + 1).is_some_and(|&b| is_ident_start(b)) => {
+                let start = i + 1;
+                let end = (start..bs.len())
+                    .find(|&j| !is_ident(bs[j]))
+                    .unwrap_or(bs.len());
+
+                rewritten.push_str(&statement[copied..i]);
+                rewritten.push('?');
+                names.push(&statement[start..end]);
+
+                copied = end;
+                i = end;
+            }
+            _ => i += 1,
+        }
+    }
+
+    rewritten.push_str(&statement[copied..]);
+
+    (rewritten, names)
+}
+
+/// Index after the closing quote, where a doubled quote or backslash escapes.
+fn skip_quoted(bs: &[u8], open: usize, quote: u8) -> usize {
+    let mut i = open + 1;
+
+    while i < bs.len() {
+        if bs[i] == b'\\' && quote == b'\'' {
+            i += 2;
+        } else if bs[i] == quote {
+            if bs.get(i + 1) == Some(&quote) {
+                i += 2;
+            } else {
+                return i + 1;
+            }
+        } else {
+            i += 1;
+        }
+    }
+
+    bs.len()
+}
+
+/// Index after the next occurrence of `end`, or the end of the statement.
+fn skip_until(bs: &[u8], from: usize, end: &[u8]) -> usize {
+    bs[from.min(bs.len())..]
+        .windows(end.len())
+        .position(|w| w == end)
+        .map(|pos| from + pos + end.len())
+        .unwrap_or(bs.len())
+}
+
+fn is_ident_start(b: u8) -> bool {
+    b.is_ascii_alphabetic() || b == b'_'
+}
+
+fn is_ident(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+/// Whether `b` can end an expression followed by a `:` path.
+fn is_path_base(b: u8) -> bool {
+    is_ident(b) || matches!(b, b']' | b')' | b'"')
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn values(names: &[&str]) -> HashMap<String, BindingValue> {
+        names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.to_string(), BindingValue::from(i as i64)))
+            .collect()
+    }
+
+    #[test]
+    fn rewrites_placeholders_in_order() {
+        let (statement, bound) = resolve(
+            "SELECT * FROM t WHERE a = :a AND b = :b_2 OR a = :a",
+            &values(&["a", "b_2"]),
+        )
+        .expect("resolving");
+
+        assert_eq!(statement, "SELECT * FROM t WHERE a = ? AND b = ? OR a = ?");
+        assert_eq!(
+            bound.iter().map(|v| v.to_string()).collect::<Vec<_>>(),
+            ["0", "1", "0"]
+        );
+    }
+
+    #[test]
+    fn skips_literals_comments_and_casts() {
+        let (statement, names) = rewrite(
+            "SELECT ':a', \"col:b\", 'it''s :c', 'esc\\' :d', $$ :e $$, x::int -- :f\n, :g /* :h */ // :i",
+        );
+
+        assert_eq!(
+            statement,
+            "SELECT ':a', \"col:b\", 'it''s :c', 'esc\\' :d', $$ :e $$, x::int -- :f\n, ? /* :h */ // :i"
+        );
+        assert_eq!(names, ["g"]);
+    }
+
+    #[test]
+    fn reports_missing_and_unused_values() {
+        assert!(matches!(
+            resolve("SELECT :a, :b", &values(&["a"])),
+            Err(BindingError::MissingValue(name)) if name == "b"
+        ));
+
+        assert!(matches!(
+            resolve("SELECT :a", &values(&["a", "c"])),
+            Err(BindingError::UnusedValue(name)) if name == "c"
+        ));
+    }
+
+    #[test]
+    fn skips_semi_structured_paths() {
+        let (statement, bound) = resolve(
+            "SELECT v:name, col:field, v:a.b, v[0]:c, f(v):d, \"V\":e FROM t WHERE id = :id",
+            &values(&["id"]),
+        )
+        .expect("resolving");
+
+        assert_eq!(
+            statement,
+            "SELECT v:name, col:field, v:a.b, v[0]:c, f(v):d, \"V\":e FROM t WHERE id = ?"
+        );
+        assert_eq!(bound.len(), 1);
+    }
+}
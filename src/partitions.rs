@@ -1,29 +1,94 @@
-use std::marker::PhantomData;
+use std::{
+    marker::PhantomData,
+    pin::Pin,
+    sync::Arc,
+    task::{ready, Context, Poll},
+};
 
-use snowsql_deserialize::{FromRow, Row};
+use futures::{
+    stream::{FuturesOrdered, FuturesUnordered},
+    Stream, StreamExt,
+};
+use snowsql_deserialize::{FromRow, Row, RowType, RowsSeed, Schema};
+use tokio_util::task::AbortOnDropHandle;
 
-use crate::{Client, ResponseOk, Result};
+use serde::de::{DeserializeSeed, IgnoredAny};
+
+use crate::{Client, Error, ResponseInfo, ResponseOk, Result};
 
 pub struct Partitions<R> {
     pub info: super::ResponseInfo,
+    /// `info.meta.row_type`, shared with the rows of each partition.
+    columns: Arc<[RowType]>,
     first_res: Option<Vec<Row<R>>>,
+    /// Index of the next partition to return, the first one being included in the response.
     next_index: usize,
     partition_count: usize,
 
     _marker: PhantomData<R>,
 }
 
+/// Position of a [`Partitions`] cursor, used to continue downloading
+/// from another process with [`Partitions::resume`].
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PartitionsState {
+    pub statement_handle: String,
+    pub partition_count: usize,
+    pub next_index: usize,
+}
+
 #[derive(Debug)]
 pub struct Partition<R> {
     pub data: Vec<R>,
+    /// Position of the partition, starting at 1.
     pub index: usize,
     pub total_count: usize,
 }
 
-#[derive(serde::Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
-struct RawPartition<R> {
-    data: Vec<R>,
+/// Deserializes the rows of a partition, `{"data": [...]}`, with the result's columns.
+struct PartitionSeed<'c, R>(&'c Arc<[RowType]>, PhantomData<R>);
+
+impl<'de, R> DeserializeSeed<'de> for PartitionSeed<'_, R>
+where
+    R: FromRow,
+{
+    type Value = Vec<Row<R>>;
+
+    fn deserialize<D>(self, des: D) -> std::result::Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        des.deserialize_map(self)
+    }
+}
+
+impl<'de, R> serde::de::Visitor<'de> for PartitionSeed<'_, R>
+where
+    R: FromRow,
+{
+    type Value = Vec<Row<R>>;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a snowflake partition")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> std::result::Result<Self::Value, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        let mut data = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "data" {
+                data = Some(map.next_value_seed(RowsSeed::new(Some(self.0)))?);
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+
+        data.ok_or_else(|| serde::de::Error::missing_field("data"))
+    }
 }
 
 impl<R> Partitions<R>
@@ -33,6 +98,7 @@ where
     pub(crate) fn from_response(r: super::Response<Row<R>>) -> Self {
         let partition_count = r.info.meta.partition_info.len();
         Self {
+            columns: r.info.meta.row_type.as_slice().into(),
             info: r.info,
             first_res: Some(r.data),
             next_index: 0,
@@ -42,36 +108,661 @@ where
         }
     }
 
+    /// Continues from a saved [`PartitionsState`], for as long as Snowflake keeps the result.
+    ///
+    /// Fetches the statement again to get its metadata,
+    /// failing with [`Error::PartitionCount`] if the result no longer has the saved number of partitions.
+    pub async fn resume(c: &Client, state: PartitionsState) -> Result<Self> {
+        Self::resume_in(c, state, None).await
+    }
+
+    /// Like [`Self::resume`], checking the result's columns against `schema` before parsing any row,
+    /// see [`crate::QueryBuilder::with_schema_validation`].
+    pub async fn resume_validated(
+        c: &Client,
+        state: PartitionsState,
+        schema: &Schema,
+    ) -> Result<Self> {
+        Self::resume_in(c, state, Some(schema)).await
+    }
+
+    async fn resume_in(
+        c: &Client,
+        state: PartitionsState,
+        schema: Option<&Schema>,
+    ) -> Result<Self> {
+        let partitions = if state.next_index == 0 {
+            let res = c.fetch_statement(&state.statement_handle).await?;
+            Self::from_response(res.snowflake_rows(schema).await?)
+        } else {
+            let info = c
+                .fetch_statement(&state.statement_handle)
+                .await?
+                .snowflake_response::<ResponseInfo>()
+                .await?;
+
+            if let Some(schema) = schema {
+                schema.validate(&info.meta.row_type)?;
+            }
+
+            Self {
+                partition_count: info.meta.partition_info.len(),
+                columns: info.meta.row_type.as_slice().into(),
+                info,
+                first_res: None,
+                next_index: state.next_index,
+
+                _marker: PhantomData,
+            }
+        };
+
+        if partitions.partition_count != state.partition_count {
+            return Err(Error::PartitionCount {
+                expected: state.partition_count,
+                actual: partitions.partition_count,
+            });
+        }
+
+        Ok(partitions)
+    }
+
+    pub fn state(&self) -> PartitionsState {
+        PartitionsState {
+            statement_handle: self.info.statement_handle.clone(),
+            partition_count: self.partition_count,
+            next_index: self.next_index,
+        }
+    }
+
+    /// Fetches the partition at `index`, counting from 1 like [`Partition::index`],
+    /// without moving the cursor used by [`Self::next`].
+    pub async fn get(&self, c: &Client, index: usize) -> Result<Option<Partition<R>>> {
+        if index == 0 || self.partition_count < index {
+            return Ok(None);
+        }
+
+        let data =
+            fetch_partition(c, &self.info.statement_handle, &self.columns, index - 1).await?;
+
+        Ok(Some(Partition {
+            data,
+            index,
+            total_count: self.partition_count,
+        }))
+    }
+
     pub async fn next(&mut self, c: &Client) -> Result<Option<Partition<R>>> {
-        let Some(rows) = self.next_rows(c).await? else {
+        let index = self.next_index;
+
+        let data = if let Some(first_res) = self.first_res.take() {
+            first_res.into_iter().map(|row| row.0).collect()
+        } else if index < self.partition_count {
+            fetch_partition(c, &self.info.statement_handle, &self.columns, index).await?
+        } else {
             return Ok(None);
         };
 
+        self.next_index += 1;
+
         Ok(Some(Partition {
-            data: rows.into_iter().map(|row| row.0).collect::<Vec<R>>(),
-            index: self.next_index + 1, // add one since the first is included in first response
+            data,
+            index: index + 1,
             total_count: self.partition_count,
         }))
     }
 
-    async fn next_rows(&mut self, c: &Client) -> Result<Option<Vec<Row<R>>>> {
-        if let Some(first_res) = self.first_res.take() {
-            return Ok(Some(first_res));
+    /// Turns the remaining partitions into a [`Stream`] that downloads partitions in the background.
+    ///
+    /// By default one partition is prefetched and partitions are returned in order.
+    pub fn stream(self, c: &Client) -> PartitionStream<R> {
+        PartitionStream {
+            client: c.clone(),
+            statement_handle: self.info.statement_handle,
+            columns: self.columns,
+            sizes: self
+                .info
+                .meta
+                .partition_info
+                .iter()
+                .map(|p| p.uncompressed_size)
+                .collect(),
+            first_res: self
+                .first_res
+                .map(|rows| rows.into_iter().map(|row| row.0).collect()),
+            next_index: self.next_index.max(1),
+            partition_count: self.partition_count,
+            num_rows: self.info.meta.num_rows,
+            prefetch: 1,
+            max_buffered_bytes: None,
+            buffered_bytes: 0,
+            in_flight: InFlight::Ordered(FuturesOrdered::new()),
+        }
+    }
+}
+
+async fn fetch_partition<R>(
+    c: &Client,
+    statement_handle: &str,
+    columns: &Arc<[RowType]>,
+    index: usize,
+) -> Result<Vec<R>>
+where
+    R: FromRow,
+{
+    let raw_rows = c
+        .retry(|_| async move {
+            c.get_partition(statement_handle, index)?
+                .send()
+                .await?
+                .snowflake_response_seed(PartitionSeed::<R>(columns, PhantomData))
+                .await
+        })
+        .await?;
+
+    Ok(raw_rows.into_iter().map(|row| row.0).collect())
+}
+
+type PartitionTask<R> = AbortOnDropHandle<(usize, Result<Vec<R>>)>;
+
+enum InFlight<R> {
+    Ordered(FuturesOrdered<PartitionTask<R>>),
+    Unordered(FuturesUnordered<PartitionTask<R>>),
+}
+
+/// Stream of partitions, created with [`Partitions::stream`].
+///
+/// Partitions are fetched on the tokio runtime,
+/// pending fetches are aborted when the stream is dropped.
+pub struct PartitionStream<R> {
+    client: Client,
+    statement_handle: String,
+    columns: Arc<[RowType]>,
+    sizes: Vec<usize>,
+    first_res: Option<Vec<R>>,
+    next_index: usize,
+    partition_count: usize,
+    num_rows: usize,
+    prefetch: usize,
+    max_buffered_bytes: Option<usize>,
+    buffered_bytes: usize,
+    in_flight: InFlight<R>,
+}
+
+impl<R> PartitionStream<R>
+where
+    R: FromRow + Send + 'static,
+{
+    /// Number of partitions to download concurrently, at least 1.
+    pub fn prefetch(mut self, count: usize) -> Self {
+        self.prefetch = count.max(1);
+        self
+    }
+
+    /// Whether partitions are returned in order (default)
+    /// or as soon as they are downloaded.
+    pub fn ordered(mut self, ordered: bool) -> Self {
+        self.in_flight = if ordered {
+            InFlight::Ordered(FuturesOrdered::new())
+        } else {
+            InFlight::Unordered(FuturesUnordered::new())
         };
+        self
+    }
 
-        self.next_index += 1;
+    /// Limits the uncompressed size of partitions being downloaded or waiting to be returned.
+    ///
+    /// A single partition is always fetched, even if it exceeds the limit on its own.
+    pub fn max_buffered_bytes(mut self, bytes: usize) -> Self {
+        self.max_buffered_bytes = Some(bytes);
+        self
+    }
 
-        if self.info.meta.partition_info.len() <= self.next_index {
-            return Ok(None);
+    /// Flattens the partitions into a stream of rows.
+    pub fn rows(self) -> RowStream<R> {
+        RowStream {
+            partitions: self,
+            rows: Vec::new().into_iter(),
+        }
+    }
+
+    fn in_flight_count(&self) -> usize {
+        match &self.in_flight {
+            InFlight::Ordered(tasks) => tasks.len(),
+            InFlight::Unordered(tasks) => tasks.len(),
+        }
+    }
+
+    fn partition_size(&self, index: usize) -> usize {
+        self.sizes.get(index).copied().unwrap_or_default()
+    }
+
+    fn start_fetches(&mut self) {
+        while self.next_index < self.partition_count {
+            let in_flight_count = self.in_flight_count();
+
+            if self.prefetch <= in_flight_count {
+                return;
+            }
+
+            let size = self.partition_size(self.next_index);
+
+            if let Some(max) = self.max_buffered_bytes
+                && 0 < in_flight_count
+                && max < self.buffered_bytes + size
+            {
+                return;
+            }
+
+            let index = self.next_index;
+            let client = self.client.clone();
+            let statement_handle = self.statement_handle.clone();
+            let columns = self.columns.clone();
+
+            let task = AbortOnDropHandle::new(tokio::spawn(async move {
+                (
+                    index,
+                    fetch_partition(&client, &statement_handle, &columns, index).await,
+                )
+            }));
+
+            match &mut self.in_flight {
+                InFlight::Ordered(tasks) => tasks.push_back(task),
+                InFlight::Unordered(tasks) => tasks.push(task),
+            }
+
+            self.buffered_bytes += size;
+            self.next_index += 1;
+        }
+    }
+}
+
+impl<R> Stream for PartitionStream<R>
+where
+    R: FromRow + Send + Unpin + 'static,
+{
+    type Item = Result<Partition<R>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        this.start_fetches();
+
+        if let Some(data) = this.first_res.take() {
+            return Poll::Ready(Some(Ok(Partition {
+                data,
+                index: 1,
+                total_count: this.partition_count,
+            })));
+        }
+
+        let next = match &mut this.in_flight {
+            InFlight::Ordered(tasks) => ready!(tasks.poll_next_unpin(cx)),
+            InFlight::Unordered(tasks) => ready!(tasks.poll_next_unpin(cx)),
+        };
+
+        let Some(joined) = next else {
+            return Poll::Ready(None);
+        };
+
+        let (index, res) = joined.unwrap_or_else(|err| std::panic::resume_unwind(err.into_panic()));
+
+        this.buffered_bytes -= this.partition_size(index);
+        this.start_fetches();
+
+        Poll::Ready(Some(
+            res.map(|data| Partition {
+                data,
+                index: index + 1,
+                total_count: this.partition_count,
+            })
+            .map_err(|err| Error::Partition {
+                index: index + 1,
+                err: Box::new(err),
+            }),
+        ))
+    }
+}
+
+/// Stream of rows across all partitions, created with [`crate::Response::rows`] or [`PartitionStream::rows`].
+///
+/// Errors are returned as [`Error::Partition`], tagged with the partition they came from.
+pub struct RowStream<R> {
+    partitions: PartitionStream<R>,
+    rows: std::vec::IntoIter<R>,
+}
+
+impl<R> RowStream<R>
+where
+    R: FromRow + Send + Unpin + 'static,
+{
+    /// Collects all remaining rows, stopping at the first error.
+    pub async fn collect_all(mut self) -> Result<Vec<R>> {
+        let mut rows = Vec::with_capacity(self.partitions.num_rows);
+
+        while let Some(row) = self.next().await {
+            rows.push(row?);
+        }
+
+        Ok(rows)
+    }
+}
+
+impl<R> Stream for RowStream<R>
+where
+    R: FromRow + Send + Unpin + 'static,
+{
+    type Item = Result<R>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(row) = this.rows.next() {
+                return Poll::Ready(Some(Ok(row)));
+            }
+
+            match ready!(this.partitions.poll_next_unpin(cx)) {
+                Some(Ok(partition)) => this.rows = partition.data.into_iter(),
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::time::Duration;
+
+    use snowsql_deserialize::RawRow;
+
+    use super::*;
+    use crate::{
+        mock::{in_order, MockResponse, MockServer},
+        Response,
+    };
+
+    /// A response whose first partition has the row `0`,
+    /// with `count` partitions in total.
+    fn response(count: usize) -> Response<Row<RawRow>> {
+        serde_json::from_value(response_json(count)).expect("deserializing response")
+    }
+
+    fn response_json(count: usize) -> serde_json::Value {
+        let partition_info = (0..count)
+            .map(|_| serde_json::json!({ "rowCount": 1, "uncompressedSize": 10 }))
+            .collect::<Vec<_>>();
+
+        serde_json::json!({
+            "resultSetMetaData": {
+                "numRows": count,
+                "format": "jsonv2",
+                "partitionInfo": partition_info,
+                "rowType": [{
+                    "name": "ID",
+                    "database": "DB",
+                    "schema": "PUBLIC",
+                    "table": "T",
+                    "byteLength": null,
+                    "type": "fixed",
+                    "scale": 0,
+                    "precision": 38,
+                    "nullable": false,
+                    "collation": null,
+                    "length": null
+                }]
+            },
+            "data": [["0"]],
+            "code": "090001",
+            "statementStatusUrl": "/api/v2/statements/h1",
+            "requestId": "r1",
+            "sqlState": "00000",
+            "statementHandle": "h1",
+            "message": "Statement executed successfully."
+        })
+    }
+
+    /// Answers partition `i` with the row `i` after `delays_ms[i]`,
+    /// or with a Snowflake error for partition `failing`.
+    fn partitions(
+        delays_ms: &'static [u64],
+        failing: Option<usize>,
+    ) -> impl Fn(&str) -> MockResponse + Send + Sync {
+        move |path| {
+            let index = path
+                .rsplit_once("partition=")
+                .and_then(|(_, index)| index.parse::<usize>().ok())
+                .expect("partition request");
+
+            if failing == Some(index) {
+                return MockResponse::new(
+                    422,
+                    r#"{"code": "000709", "message": "partition expired", "sqlState": "02000"}"#,
+                );
+            }
+
+            MockResponse::new(200, format!(r#"{{"data": [["{index}"]]}}"#))
+                .delayed(Duration::from_millis(delays_ms[index]))
+        }
+    }
+
+    async fn indices(mut stream: PartitionStream<RawRow>) -> Vec<usize> {
+        let mut indices = Vec::new();
+
+        while let Some(partition) = stream.next().await {
+            let partition = partition.expect("fetching partition");
+
+            assert_eq!(
+                partition.data[0].0[0].as_deref(),
+                Some((partition.index - 1).to_string().as_str())
+            );
+            indices.push(partition.index);
+        }
+
+        indices
+    }
+
+    #[tokio::test]
+    async fn ordered_stream_waits_for_earlier_partitions() {
+        let server = MockServer::start(partitions(&[0, 200, 0, 100], None)).await;
+
+        let stream = response(4)
+            .partitions()
+            .stream(&server.client())
+            .prefetch(3);
+
+        assert_eq!(indices(stream).await, [1, 2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn unordered_stream_returns_partitions_as_they_complete() {
+        let server = MockServer::start(partitions(&[0, 200, 0, 100], None)).await;
+
+        let stream = response(4)
+            .partitions()
+            .stream(&server.client())
+            .prefetch(3)
+            .ordered(false);
+
+        assert_eq!(indices(stream).await, [1, 3, 4, 2]);
+    }
+
+    #[tokio::test]
+    async fn prefetch_and_buffered_bytes_limit_requests() {
+        let server = MockServer::start(partitions(&[0, 0, 0, 0, 0], None)).await;
+        let c = server.client();
+
+        let mut stream = response(5).partitions().stream(&c).prefetch(3);
+        stream.next().await.expect("first partition").unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(server.requests().len(), 3);
+
+        drop(stream);
+
+        // each partition is 10 bytes, so only one fits in 15
+        let server = MockServer::start(partitions(&[0, 0, 0, 0, 0], None)).await;
+        let mut stream = response(5)
+            .partitions()
+            .stream(&server.client())
+            .prefetch(3)
+            .max_buffered_bytes(15);
+        stream.next().await.expect("first partition").unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(server.requests().len(), 1);
+
+        assert_eq!(indices(stream).await, [2, 3, 4, 5]);
+        assert_eq!(server.requests().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn failed_partition_is_reported_with_its_index() {
+        let server = MockServer::start(partitions(&[0, 0, 0], Some(1))).await;
+
+        let mut stream = response(3).partitions().stream(&server.client());
+
+        assert_eq!(stream.next().await.unwrap().unwrap().index, 1);
+
+        match stream.next().await {
+            Some(Err(Error::Partition { index, err })) => {
+                assert_eq!(index, 2);
+                assert_eq!(err.as_snowflake().unwrap().code, "000709");
+            }
+            res => panic!("expected a partition error, got {res:?}"),
         }
 
-        let raw_rows = c
-            .get_partition(&self.info.statement_handle, self.next_index)?
-            .send()
-            .await?
-            .snowflake_response::<RawPartition<Row<R>>>()
-            .await?;
+        assert_eq!(stream.next().await.unwrap().unwrap().index, 3);
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn partition_request_is_retried() {
+        let server = MockServer::start(in_order([
+            MockResponse::new(503, "unavailable"),
+            MockResponse::new(200, r#"{"data": [["1"]]}"#).truncated(),
+            MockResponse::new(200, r#"{"data": [["1"]]}"#),
+        ]))
+        .await;
 
-        Ok(Some(raw_rows.data))
+        let partition = response(2)
+            .partitions()
+            .get(&server.retrying_client(), 2)
+            .await
+            .expect("retrying")
+            .expect("second partition");
+
+        assert_eq!(partition.data[0].0[0].as_deref(), Some("1"));
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests
+            .iter()
+            .all(|req| req.ends_with("/statements/h1?partition=1")));
+    }
+
+    #[tokio::test]
+    async fn collect_all_keeps_row_order() {
+        let server = MockServer::start(partitions(&[0, 200, 0, 100], None)).await;
+
+        let rows = response(4)
+            .partitions()
+            .stream(&server.client())
+            .prefetch(3)
+            .rows()
+            .collect_all()
+            .await
+            .expect("collecting rows");
+
+        let ids = rows
+            .into_iter()
+            .map(|row| row.0[0].clone().unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(ids, ["0", "1", "2", "3"]);
+    }
+
+    #[tokio::test]
+    async fn row_stream_reports_partition_errors() {
+        let server = MockServer::start(partitions(&[0, 0, 0], Some(2))).await;
+        let c = server.client();
+
+        let mut rows = response(3).rows(&c);
+
+        assert_eq!(
+            rows.next().await.unwrap().unwrap().0[0].as_deref(),
+            Some("0")
+        );
+        assert_eq!(
+            rows.next().await.unwrap().unwrap().0[0].as_deref(),
+            Some("1")
+        );
+        assert!(matches!(
+            rows.next().await,
+            Some(Err(Error::Partition { index: 3, .. }))
+        ));
+
+        let err = response(3)
+            .rows(&c)
+            .collect_all()
+            .await
+            .expect_err("partition 3 fails");
+        assert!(matches!(err, Error::Partition { index: 3, .. }));
+    }
+
+    #[tokio::test]
+    async fn resume_checks_partition_count() {
+        let body = response_json(3).to_string();
+        let server = MockServer::start(move |_| MockResponse::new(200, body.clone())).await;
+        let c = server.client();
+
+        let state = |partition_count, next_index| PartitionsState {
+            statement_handle: "h1".into(),
+            partition_count,
+            next_index,
+        };
+
+        for next_index in [0, 2] {
+            let partitions = Partitions::<RawRow>::resume(&c, state(3, next_index))
+                .await
+                .expect("resuming");
+            assert_eq!(partitions.state(), state(3, next_index));
+
+            assert!(matches!(
+                Partitions::<RawRow>::resume(&c, state(4, next_index)).await,
+                Err(Error::PartitionCount {
+                    expected: 4,
+                    actual: 3
+                })
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn resume_validates_columns() {
+        const TEXT_SCHEMA: Schema = Schema {
+            columns: &[snowsql_deserialize::ColumnSchema {
+                name: "ID",
+                nullable: false,
+                accepts: |col| col.data_type == "text",
+            }],
+            by_name: false,
+        };
+
+        let body = response_json(3).to_string();
+        let server = MockServer::start(move |_| MockResponse::new(200, body.clone())).await;
+        let c = server.client();
+
+        for next_index in [0, 2] {
+            let state = PartitionsState {
+                statement_handle: "h1".into(),
+                partition_count: 3,
+                next_index,
+            };
+
+            assert!(matches!(
+                Partitions::<RawRow>::resume_validated(&c, state, &TEXT_SCHEMA).await,
+                Err(Error::Schema(_))
+            ));
+        }
     }
 }
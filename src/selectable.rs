@@ -1,4 +1,4 @@
-use snowsql_deserialize::{FromRow, RawRow};
+use snowsql_deserialize::{FromRow, RawRow, Schema};
 
 use crate::QueryBuilder;
 
@@ -11,6 +11,8 @@ where
     const TABLE_NAME: &str;
     const SELECT: &str;
     const ORDER_BY: &str;
+    /// Columns and types expected in the result, see [`crate::QueryBuilder::with_schema_validation`].
+    const SCHEMA: Schema = Schema::ANY;
 
     /// Starts a query by selecting all fields in the Selectable struct
     /// from the provided table_source
@@ -0,0 +1,214 @@
+use std::fmt;
+
+use reqwest::StatusCode;
+use serde::de::IgnoredAny;
+use snowsql_deserialize::{FromRow, Row, Schema};
+
+use crate::{data_manipulation::DataManipulationResult, Client, Response, ResponseOk, Result};
+
+/// Handle of a statement submitted to Snowflake.
+///
+/// Returned by [`crate::QueryBuilder::submit`], which does not wait for the statement to finish.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StatementHandle(String);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatementStatus {
+    Running,
+    Complete,
+}
+
+/// Body of a `202 Accepted` response, returned while a statement is still executing.
+#[derive(serde::Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct QueryStatus {
+    pub statement_handle: String,
+}
+
+/// Response of a request executing multiple statements.
+#[derive(serde::Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MultiStatementResponse {
+    pub statement_handles: Vec<String>,
+}
+
+impl StatementHandle {
+    pub fn new(handle: impl Into<String>) -> Self {
+        Self(handle.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Checks the status once, without waiting.
+    ///
+    /// A failed statement is returned as an error.
+    /// The result of a complete statement is not downloaded.
+    pub async fn status(&self, c: &Client) -> Result<StatementStatus> {
+        c.retry(|_| async {
+            let res = c.get_statement(self.as_str())?.send().await?;
+            let status = res.status();
+
+            if status == StatusCode::ACCEPTED {
+                Ok(StatementStatus::Running)
+            } else if status.is_success() {
+                Ok(StatementStatus::Complete)
+            } else {
+                Err(crate::nok_error(status, &res.bytes().await?))
+            }
+        })
+        .await
+    }
+
+    /// Polls the statement, using the client's poll backoff, until it is no longer running.
+    pub async fn wait(&self, c: &Client) -> Result<()> {
+        c.fetch_statement(self.as_str())
+            .await?
+            .snowflake_response::<IgnoredAny>()
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn cancel(&self, c: &Client) -> Result<()> {
+        c.cancel(self).await
+    }
+
+    /// Waits for the statement to finish and returns the first partition of its result.
+    pub async fn result<R>(&self, c: &Client) -> Result<Response<Row<R>>>
+    where
+        R: FromRow,
+    {
+        c.fetch(self).await
+    }
+
+    /// Like [`Self::result`], checking the result's columns against `schema` before parsing any row.
+    pub async fn result_validated<R>(&self, c: &Client, schema: &Schema) -> Result<Response<Row<R>>>
+    where
+        R: FromRow,
+    {
+        c.fetch_validated(self, schema).await
+    }
+
+    /// Waits for a `delete`, `insert` or `update` statement to finish and returns its changes.
+    pub async fn manipulation_result(&self, c: &Client) -> Result<DataManipulationResult> {
+        c.fetch_statement(self.as_str())
+            .await?
+            .snowflake_response::<DataManipulationResult>()
+            .await
+    }
+}
+
+impl From<String> for StatementHandle {
+    fn from(handle: String) -> Self {
+        Self(handle)
+    }
+}
+
+impl AsRef<str> for StatementHandle {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for StatementHandle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{
+        mock::{in_order, MockResponse, MockServer},
+        SnowflakeError,
+    };
+
+    fn running() -> MockResponse {
+        MockResponse::new(202, r#"{"statementHandle": "h1"}"#)
+    }
+
+    fn complete() -> MockResponse {
+        MockResponse::new(200, r#"{"statementHandle": "h1"}"#)
+    }
+
+    fn failed() -> MockResponse {
+        MockResponse::new(
+            422,
+            r#"{"code": "000630", "message": "timeout", "sqlState": "57014", "statementHandle": "h1"}"#,
+        )
+    }
+
+    #[tokio::test]
+    async fn wait_polls_until_complete() {
+        let server = MockServer::start(in_order([running(), running(), complete()])).await;
+
+        StatementHandle::new("h1")
+            .wait(&server.client())
+            .await
+            .expect("waiting");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests
+            .iter()
+            .all(|req| req.starts_with("GET ") && req.ends_with("/statements/h1")));
+    }
+
+    #[tokio::test]
+    async fn wait_returns_failed_statement() {
+        let server = MockServer::start(in_order([running(), failed()])).await;
+
+        let err = StatementHandle::new("h1")
+            .wait(&server.client())
+            .await
+            .expect_err("statement failed");
+
+        assert!(err.as_snowflake().is_some_and(SnowflakeError::is_timeout));
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn status_goes_from_running_to_complete() {
+        let server = MockServer::start(in_order([running(), complete(), failed()])).await;
+        let c = server.client();
+        let handle = StatementHandle::new("h1");
+
+        assert_eq!(handle.status(&c).await.unwrap(), StatementStatus::Running);
+        assert_eq!(handle.status(&c).await.unwrap(), StatementStatus::Complete);
+        assert!(handle.status(&c).await.is_err());
+
+        // status never polls
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn status_does_not_read_result() {
+        // reading the body would fail
+        let server = MockServer::start(in_order([complete().truncated()])).await;
+
+        let status = StatementHandle::new("h1")
+            .status(&server.client())
+            .await
+            .expect("checking status");
+
+        assert_eq!(status, StatementStatus::Complete);
+    }
+
+    #[tokio::test]
+    async fn cancel_posts_to_statement() {
+        let server = MockServer::start(in_order([complete()])).await;
+
+        StatementHandle::new("h1")
+            .cancel(&server.client())
+            .await
+            .expect("cancelling");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("POST ") && requests[0].ends_with("/statements/h1/cancel"));
+    }
+}
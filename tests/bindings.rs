@@ -0,0 +1,30 @@
+use snowsql::{BindingKind, BindingValue};
+
+#[test]
+fn bind_optional_values() {
+    let some = BindingValue::from(Some(23_i64));
+    assert!(matches!(some.kind(), BindingKind::Fixed));
+    assert_eq!(some.value().as_deref(), Some("23"));
+
+    let none = BindingValue::from(None::<i64>);
+    assert!(matches!(none.kind(), BindingKind::Fixed));
+    assert_eq!(none.value(), None);
+
+    let none = BindingValue::from(None::<String>);
+    assert!(matches!(none.kind(), BindingKind::Text));
+    assert_eq!(none.value(), None);
+
+    let none = BindingValue::from(None::<&str>);
+    assert!(matches!(none.kind(), BindingKind::Text));
+
+    let none = BindingValue::from(None::<f64>);
+    assert!(matches!(none.kind(), BindingKind::Real));
+}
+
+#[test]
+fn statement_has_named_placeholders_rewritten() {
+    let query =
+        snowsql::sql::<snowsql::RawRow>("SELECT v:name FROM t WHERE id = :id").bind("id", 1);
+
+    assert_eq!(query.as_statement(), "SELECT v:name FROM t WHERE id = ?");
+}
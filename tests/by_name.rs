@@ -0,0 +1,100 @@
+use snowsql::{FromRow, Response, Row};
+
+#[allow(dead_code)]
+#[derive(Debug, FromRow)]
+#[snowsql(by_name)]
+struct Site {
+    site_name: String,
+    client_id: u32,
+    #[snowsql(default)]
+    num_permits: Option<i64>,
+}
+
+#[test]
+fn deserialize_by_name() {
+    let res = serde_json::from_str::<Response<Row<Site>>>(&example(false))
+        .expect("deserializing response");
+
+    assert_eq!(res.data.len(), 2);
+    assert_eq!(res.data[0].0.client_id, 3);
+    assert_eq!(res.data[0].0.site_name, "Parkando");
+    assert_eq!(res.data[0].0.num_permits, None);
+    assert_eq!(res.data[1].0.client_id, 4);
+    assert_eq!(res.data[1].0.site_name, "OtherSite");
+    assert_eq!(res.info.meta.row_type.len(), 3);
+}
+
+#[test]
+fn deserialize_by_name_with_data_first() {
+    let res = serde_json::from_str::<Response<Row<Site>>>(&example(true))
+        .expect("deserializing response");
+
+    assert_eq!(res.data[1].0.site_name, "OtherSite");
+    assert_eq!(
+        res.info.statement_handle,
+        "01ad9ea3-3201-dca3-0000-a219000bb062"
+    );
+}
+
+#[test]
+fn report_missing_column() {
+    #[allow(dead_code)]
+    #[derive(Debug, FromRow)]
+    #[snowsql(by_name)]
+    struct Missing {
+        site_id: String,
+    }
+
+    let err = serde_json::from_str::<Response<Row<Missing>>>(&example(false))
+        .expect_err("deserializing response");
+
+    assert!(
+        err.to_string().contains("column `site_id` not found"),
+        "{err}"
+    );
+}
+
+#[test]
+fn report_invalid_column_by_name() {
+    let example = example(false).replace(r#""Parkando""#, "5");
+
+    let err =
+        serde_json::from_str::<Response<Row<Site>>>(&example).expect_err("deserializing response");
+
+    assert!(err.to_string().contains("Column `SITE_NAME`"), "{err}");
+}
+
+fn example(data_first: bool) -> String {
+    let data = r#""data": [["unused", "Parkando", "3"], ["unused", "OtherSite", "4"]]"#;
+
+    let meta = r#""resultSetMetaData": {
+    "numRows": 2,
+    "format": "jsonv2",
+    "partitionInfo": [{ "rowCount": 2, "uncompressedSize": 64 }],
+    "rowType": [
+      { "name": "EXTRA", "database": "DB", "schema": "PUBLIC", "table": "SITES", "byteLength": 16, "type": "text", "scale": null, "precision": null, "nullable": true },
+      { "name": "SITE_NAME", "database": "DB", "schema": "PUBLIC", "table": "SITES", "byteLength": 16, "type": "text", "scale": null, "precision": null, "nullable": false },
+      { "name": "CLIENT_ID", "database": "DB", "schema": "PUBLIC", "table": "SITES", "byteLength": null, "type": "fixed", "scale": 0, "precision": 38, "nullable": false }
+    ]
+  }"#;
+
+    let (first, second) = if data_first {
+        (data, meta)
+    } else {
+        (meta, data)
+    };
+
+    format!(
+        r#"{{
+  {first},
+  {second},
+  "code": "090001",
+  "statementStatusUrl": "/api/v2/statements/01ad9ea3-3201-dca3-0000-a219000bb062",
+  "requestId": "7a7cb2b5-8f4b-4f49-9875-32576d808de2",
+  "sqlState": "00000",
+  "statementHandle": "01ad9ea3-3201-dca3-0000-a219000bb062",
+  "message": "Statement executed successfully.",
+  "createdOn": 1700000000000
+}}"#
+    )
+}
@@ -0,0 +1,69 @@
+use snowsql::{DynamicRow, Response, Row, SnowflakeType, Timestamp, Value};
+
+#[test]
+fn deserialize_dynamic_rows() {
+    let res =
+        serde_json::from_str::<Response<Row<DynamicRow>>>(EXAMPLE).expect("deserializing response");
+
+    assert_eq!(res.data.len(), 2);
+
+    let row = &res.data[0].0;
+    assert_eq!(row.len(), 5);
+    assert_eq!(row.get(0), Some(&Value::Int(3)));
+    assert_eq!(
+        row.get("CLIENT_NAME"),
+        Some(&Value::Text("Parkando".into()))
+    );
+    assert_eq!(row.get("amount"), Some(&Value::Decimal("12.50".into())));
+    assert_eq!(
+        row.get("CREATED_AT"),
+        Some(&Value::Timestamp(Timestamp {
+            epoch_nanos: 1_700_000_000_123_000_000,
+            offset_minutes: Some(-300),
+        }))
+    );
+    assert_eq!(
+        row.get("TAGS"),
+        Some(&Value::Json(serde_json::json!(["a", "b"])))
+    );
+    assert_eq!(row.get("MISSING"), None);
+    assert_eq!(row.get(5), None);
+
+    let row = &res.data[1].0;
+    assert_eq!(row.get("AMOUNT"), Some(&Value::Null));
+    assert_eq!(
+        row.columns()[0].snowflake_type,
+        SnowflakeType::Fixed {
+            precision: 38,
+            scale: 0
+        }
+    );
+}
+
+static EXAMPLE: &str = r#"
+{
+  "resultSetMetaData": {
+    "numRows": 2,
+    "format": "jsonv2",
+    "partitionInfo": [{ "rowCount": 2, "uncompressedSize": 180 }],
+    "rowType": [
+      { "name": "CLIENT_ID", "database": "DB", "schema": "PUBLIC", "table": "T", "byteLength": null, "type": "fixed", "scale": 0, "precision": 38, "nullable": false },
+      { "name": "CLIENT_NAME", "database": "DB", "schema": "PUBLIC", "table": "T", "byteLength": 64, "length": 16, "type": "text", "scale": null, "precision": null, "nullable": false },
+      { "name": "AMOUNT", "database": "DB", "schema": "PUBLIC", "table": "T", "byteLength": null, "type": "fixed", "scale": 2, "precision": 10, "nullable": true },
+      { "name": "CREATED_AT", "database": "DB", "schema": "PUBLIC", "table": "T", "byteLength": null, "type": "timestamp_tz", "scale": 3, "precision": 0, "nullable": false },
+      { "name": "TAGS", "database": "DB", "schema": "PUBLIC", "table": "T", "byteLength": null, "type": "array", "scale": null, "precision": null, "nullable": true }
+    ]
+  },
+  "data": [
+    ["3", "Parkando", "12.50", "1700000000.123 1140", "[\"a\", \"b\"]"],
+    ["4", "OtherCompany", null, "1700000000.000 1440", null]
+  ],
+  "code": "090001",
+  "statementStatusUrl": "/api/v2/statements/01ad9ea3-3201-dca3-0000-a219000bb062",
+  "requestId": "7a7cb2b5-8f4b-4f49-9875-32576d808de2",
+  "sqlState": "00000",
+  "statementHandle": "01ad9ea3-3201-dca3-0000-a219000bb062",
+  "message": "Statement executed successfully.",
+  "createdOn": 1700000000000
+}
+"#;
@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+use snowsql::{
+    BindRow, BindingKind, BindingValue, FromRow, Insertable, Json, Mergeable, Row, Selectable,
+};
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Settings {
+    theme: String,
+    notifications: bool,
+}
+
+#[test]
+fn deserialize_json_columns() {
+    #[derive(Debug, FromRow)]
+    struct User {
+        #[snowsql(json)]
+        settings: Settings,
+        #[snowsql(json)]
+        previous: Option<Settings>,
+        tags: Json<Vec<String>>,
+        raw: serde_json::Value,
+    }
+
+    let user = serde_json::from_str::<Row<User>>(
+        r#"["{\n  \"theme\": \"dark\",\n  \"notifications\": true\n}", null, "[\"a\"]", null]"#,
+    )
+    .expect("deserializing row")
+    .0;
+
+    assert_eq!(
+        user.settings,
+        Settings {
+            theme: "dark".into(),
+            notifications: true
+        }
+    );
+    assert_eq!(user.previous, None);
+    assert_eq!(user.tags.0, ["a"]);
+    assert_eq!(user.raw, serde_json::Value::Null);
+}
+
+#[test]
+fn bind_json_values() {
+    let value = BindingValue::try_from(Json(vec![1, 2])).unwrap();
+    assert!(matches!(value.kind(), BindingKind::Text));
+    assert_eq!(value.value().as_deref(), Some("[1,2]"));
+
+    let null = BindingValue::try_from(Json(None::<Settings>)).unwrap();
+    assert_eq!(null.value(), None);
+
+    #[derive(Insertable, Mergeable)]
+    #[snowsql(table_name = "users")]
+    struct User {
+        #[snowsql(key)]
+        id: i64,
+        #[snowsql(json)]
+        settings: Settings,
+    }
+
+    assert_eq!(
+        User::INSERT,
+        "INSERT INTO users (id, settings) SELECT column1, PARSE_JSON(column2) FROM VALUES (?, ?)"
+    );
+    assert_eq!(User::INSERT_VALUES_ROW, Some("(?, ?)"));
+    assert_eq!(
+        User::insert_statement(3),
+        "INSERT INTO users (id, settings) SELECT column1, PARSE_JSON(column2) FROM VALUES (?, ?), (?, ?), (?, ?)"
+    );
+
+    let user = User {
+        id: 7,
+        settings: Settings {
+            theme: "dark".into(),
+            notifications: false,
+        },
+    };
+    assert_eq!(
+        user.bind_row()
+            .unwrap()
+            .iter()
+            .map(BindingValue::to_string)
+            .collect::<Vec<_>>(),
+        ["7", r#"{"theme":"dark","notifications":false}"#]
+    );
+    assert_eq!(
+        User::merge_statement(2),
+        "MERGE INTO users AS target USING (SELECT column1 AS id, PARSE_JSON(column2) AS settings FROM VALUES (?, ?), (?, ?)) AS source ON target.id = source.id WHEN MATCHED THEN UPDATE SET target.settings = source.settings WHEN NOT MATCHED THEN INSERT (id, settings) VALUES (source.id, source.settings)"
+    );
+}
+
+#[test]
+fn unserializable_json_is_invalid() {
+    let map = HashMap::from([((1, 2), "a")]);
+
+    let err = BindingValue::try_from(Json(map)).expect_err("serializing a map with tuple keys");
+    assert!(err.to_string().contains("key must be a string"), "{err}");
+}
+
+#[test]
+fn json_columns_are_nullable_when_optional() {
+    #[allow(dead_code)]
+    #[derive(FromRow, Selectable)]
+    #[snowsql(table_name = "users", order_by = "id")]
+    struct User {
+        id: i64,
+        #[snowsql(json)]
+        settings: Settings,
+        #[snowsql(json)]
+        previous: Option<Settings>,
+        #[snowsql(json, default)]
+        tags: Vec<String>,
+    }
+
+    let nullable = User::SCHEMA
+        .columns
+        .iter()
+        .map(|col| col.nullable)
+        .collect::<Vec<_>>();
+
+    assert_eq!(nullable, [false, false, true, true]);
+}
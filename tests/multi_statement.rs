@@ -0,0 +1,76 @@
+use snowsql::{RawRow, Response, Row, StatementParameters};
+
+#[test]
+fn deserialize_multi_statement_response() {
+    let res =
+        serde_json::from_str::<Response<Row<RawRow>>>(EXAMPLE).expect("deserializing response");
+
+    assert_eq!(
+        res.info.statement_handles,
+        [
+            "01b2a4b6-0000-f6f1-0000-a21900e1a0a2",
+            "01b2a4b6-0000-f6f1-0000-a21900e1a0a6",
+            "01b2a4b6-0000-f6f1-0000-a21900e1a0aa",
+        ]
+    );
+}
+
+#[test]
+fn serialize_statement_count() {
+    let params = StatementParameters {
+        statement_count: Some(0),
+        ..Default::default()
+    };
+
+    let json = serde_json::to_value(params).expect("serializing parameters");
+    assert_eq!(json["multi_statement_count"], 0);
+
+    let json =
+        serde_json::to_value(StatementParameters::default()).expect("serializing parameters");
+    assert!(json.get("multi_statement_count").is_none());
+}
+
+static EXAMPLE: &str = r#"
+{
+  "resultSetMetaData": {
+    "numRows": 1,
+    "format": "jsonv2",
+    "partitionInfo": [
+      {
+        "rowCount": 1,
+        "uncompressedSize": 48
+      }
+    ],
+    "rowType": [
+      {
+        "name": "multiple statement execution",
+        "database": "",
+        "schema": "",
+        "table": "",
+        "byteLength": 16777216,
+        "type": "text",
+        "scale": null,
+        "precision": null,
+        "nullable": false,
+        "collation": null,
+        "length": 16777216
+      }
+    ]
+  },
+  "data": [
+    ["Multiple statements executed successfully."]
+  ],
+  "code": "090001",
+  "statementHandles": [
+    "01b2a4b6-0000-f6f1-0000-a21900e1a0a2",
+    "01b2a4b6-0000-f6f1-0000-a21900e1a0a6",
+    "01b2a4b6-0000-f6f1-0000-a21900e1a0aa"
+  ],
+  "statementStatusUrl": "/api/v2/statements/01b2a4b6-0000-f6f1-0000-a21900e1a09e?requestId=3f2b8d0e-6d7a-4a0b-9c6f-1d2e3f4a5b6c",
+  "requestId": "3f2b8d0e-6d7a-4a0b-9c6f-1d2e3f4a5b6c",
+  "sqlState": "00000",
+  "statementHandle": "01b2a4b6-0000-f6f1-0000-a21900e1a09e",
+  "message": "Statement executed successfully.",
+  "createdOn": 1712345678901
+}
+"#;
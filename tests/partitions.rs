@@ -0,0 +1,63 @@
+use snowsql::{PartitionsState, RawRow, Response, Row};
+
+#[test]
+fn partitions_state_roundtrip() {
+    let res =
+        serde_json::from_str::<Response<Row<RawRow>>>(EXAMPLE).expect("deserializing response");
+
+    let state = res.partitions().state();
+
+    assert_eq!(
+        state,
+        PartitionsState {
+            statement_handle: "01ad9ea3-3201-dca3-0000-a219000bb062".into(),
+            partition_count: 3,
+            next_index: 0,
+        }
+    );
+
+    let json = serde_json::to_string(&state).expect("serializing state");
+    assert_eq!(
+        serde_json::from_str::<PartitionsState>(&json).expect("deserializing state"),
+        state
+    );
+}
+
+static EXAMPLE: &str = r#"
+{
+  "resultSetMetaData": {
+    "numRows": 3,
+    "format": "jsonv2",
+    "partitionInfo": [
+      { "rowCount": 1, "uncompressedSize": 12 },
+      { "rowCount": 1, "uncompressedSize": 12 },
+      { "rowCount": 1, "uncompressedSize": 12 }
+    ],
+    "rowType": [
+      {
+        "name": "CLIENT_ID",
+        "database": "M46_DATA_SHARE_PARKING",
+        "schema": "PUBLIC",
+        "table": "SPOTS_AND_AGREEMENTS",
+        "byteLength": null,
+        "type": "fixed",
+        "scale": 0,
+        "precision": 38,
+        "nullable": false,
+        "collation": null,
+        "length": null
+      }
+    ]
+  },
+  "data": [
+    ["3"]
+  ],
+  "code": "090001",
+  "statementStatusUrl": "/api/v2/statements/01ad9ea3-3201-dca3-0000-a219000bb062?requestId=0a404baa-8f14-45f1-894c-a4f8ab7ca9de",
+  "requestId": "0a404baa-8f14-45f1-894c-a4f8ab7ca9de",
+  "sqlState": "00000",
+  "statementHandle": "01ad9ea3-3201-dca3-0000-a219000bb062",
+  "message": "Statement executed successfully.",
+  "createdOn": 1689333321982
+}
+"#;
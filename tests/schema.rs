@@ -0,0 +1,92 @@
+use snowsql::{FromRow, RowType, SchemaIssue, Selectable, SnowflakeType};
+
+#[allow(dead_code)]
+#[derive(FromRow, Selectable)]
+#[snowsql(table_name = "sites", order_by = "client_id")]
+struct Site {
+    client_id: u32,
+    site_name: String,
+    num_permits: Option<i64>,
+}
+
+fn columns(json: &str) -> Vec<RowType> {
+    serde_json::from_str(json).expect("deserializing row types")
+}
+
+#[test]
+fn matching_schema() {
+    let columns = columns(
+        r#"[
+      { "name": "CLIENT_ID", "database": "DB", "schema": "PUBLIC", "table": "SITES", "byteLength": null, "type": "fixed", "scale": 0, "precision": 38, "nullable": false },
+      { "name": "SITE_NAME", "database": "DB", "schema": "PUBLIC", "table": "SITES", "byteLength": 16, "type": "text", "scale": null, "precision": null, "nullable": false },
+      { "name": "NUM_PERMITS", "database": "DB", "schema": "PUBLIC", "table": "SITES", "byteLength": null, "type": "fixed", "scale": 0, "precision": 38, "nullable": true }
+    ]"#,
+    );
+
+    Site::SCHEMA.validate(&columns).expect("validating schema");
+}
+
+#[test]
+fn mismatching_schema() {
+    let columns = columns(
+        r#"[
+      { "name": "CLIENT_ID", "database": "DB", "schema": "PUBLIC", "table": "SITES", "byteLength": null, "type": "fixed", "scale": 0, "precision": 38, "nullable": true },
+      { "name": "SITE_NAME", "database": "DB", "schema": "PUBLIC", "table": "SITES", "byteLength": null, "type": "fixed", "scale": 0, "precision": 38, "nullable": false }
+    ]"#,
+    );
+
+    let err = Site::SCHEMA
+        .validate(&columns)
+        .expect_err("validating schema");
+
+    assert_eq!(
+        err.issues,
+        [
+            SchemaIssue::ColumnCount {
+                expected: 3,
+                actual: 2
+            },
+            SchemaIssue::Nullable {
+                column: "client_id"
+            },
+            SchemaIssue::Type {
+                column: "site_name",
+                data_type: "NUMBER(38,0)".into()
+            },
+            SchemaIssue::MissingColumn {
+                column: "num_permits"
+            },
+        ]
+    );
+}
+
+#[test]
+fn parse_snowflake_types() {
+    let columns = columns(
+        r#"[
+      { "name": "A", "database": "DB", "schema": "PUBLIC", "table": "T", "byteLength": null, "type": "fixed", "scale": 2, "precision": 10, "nullable": true },
+      { "name": "B", "database": "DB", "schema": "PUBLIC", "table": "T", "byteLength": 64, "length": 16, "type": "text", "scale": null, "precision": null, "nullable": true },
+      { "name": "C", "database": "DB", "schema": "PUBLIC", "table": "T", "byteLength": null, "type": "timestamp_tz", "scale": 9, "precision": 0, "nullable": true },
+      { "name": "D", "database": "DB", "schema": "PUBLIC", "table": "T", "byteLength": null, "type": "variant", "scale": null, "precision": null, "nullable": true },
+      { "name": "E", "database": "DB", "schema": "PUBLIC", "table": "T", "byteLength": null, "type": "decfloat", "scale": null, "precision": null, "nullable": true }
+    ]"#,
+    );
+
+    assert_eq!(
+        columns
+            .iter()
+            .map(|col| col.snowflake_type.clone())
+            .collect::<Vec<_>>(),
+        [
+            SnowflakeType::Fixed {
+                precision: 10,
+                scale: 2
+            },
+            SnowflakeType::Text { length: Some(16) },
+            SnowflakeType::TimestampTz { scale: 9 },
+            SnowflakeType::Variant,
+            SnowflakeType::Unknown("decfloat".into()),
+        ]
+    );
+    assert_eq!(columns[0].snowflake_type.to_string(), "NUMBER(10,2)");
+}
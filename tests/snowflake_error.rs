@@ -0,0 +1,52 @@
+use snowsql::SnowflakeError;
+
+fn parse(body: &str) -> SnowflakeError {
+    serde_json::from_str(body).expect("deserializing error body")
+}
+
+#[test]
+fn deserialize_compilation_error() {
+    let err = parse(
+        r#"{
+  "code": "002003",
+  "message": "SQL compilation error:\nObject 'M46_DATA_SHARE_PARKING.PUBLIC.NOPE' does not exist or not authorized.",
+  "sqlState": "02000",
+  "statementHandle": "01ad9ea3-3201-dca3-0000-a219000bb062",
+  "statementStatusUrl": "/api/v2/statements/01ad9ea3-3201-dca3-0000-a219000bb062"
+}"#,
+    );
+
+    assert_eq!(err.code, "002003");
+    assert_eq!(err.sql_state.as_deref(), Some("02000"));
+    assert_eq!(
+        err.statement_handle.as_deref(),
+        Some("01ad9ea3-3201-dca3-0000-a219000bb062")
+    );
+    assert!(err.is_object_not_found());
+    assert!(!err.is_syntax_error());
+    assert!(!err.is_auth_failure());
+    assert!(!err.is_timeout());
+}
+
+#[test]
+fn classify_errors() {
+    let syntax = parse(
+        r#"{"code": "001003", "message": "SQL compilation error:\nsyntax error line 1 at position 0 unexpected 'SELEC'.", "sqlState": "42000"}"#,
+    );
+    assert!(syntax.is_syntax_error());
+
+    let access = parse(
+        r#"{"code": "003001", "message": "SQL access control error:\nInsufficient privileges to operate on table 'T'", "sqlState": "42000"}"#,
+    );
+    assert!(!access.is_syntax_error());
+
+    let auth = parse(r#"{"code": "390144", "message": "JWT token is invalid."}"#);
+    assert!(auth.is_auth_failure());
+    assert_eq!(auth.sql_state, None);
+
+    let timeout = parse(
+        r#"{"code": "000630", "message": "Statement reached its statement or warehouse timeout of 10 second(s) and was canceled.", "sqlState": "57014"}"#,
+    );
+    assert!(timeout.is_timeout());
+    assert!(!timeout.is_syntax_error());
+}
@@ -1,4 +1,4 @@
-use snowsql::{FromRow, Selectable};
+use snowsql::{BindRow, BindingValue, FromRow, Insertable, Mergeable, Selectable};
 
 #[test]
 fn derive_selectable() {
@@ -25,3 +25,189 @@ fn derive_selectable() {
     assert_eq!(TestStructOrderByInStructAttr::TABLE_NAME, "smtelse");
     assert_eq!(TestStructOrderByInStructAttr::ORDER_BY, "vafan");
 }
+
+#[test]
+fn derive_insertable() {
+    #[derive(Insertable)]
+    #[snowsql(table_name = "people")]
+    struct Person {
+        #[snowsql(skip_insert)]
+        #[allow(dead_code)]
+        id: i64,
+        name: String,
+        age: Option<i32>,
+    }
+
+    assert_eq!(
+        Person::INSERT,
+        "INSERT INTO people (name, age) VALUES (?, ?)"
+    );
+
+    let person = Person {
+        id: 1,
+        name: "Ada".into(),
+        age: None,
+    };
+
+    assert_eq!(
+        person
+            .bind_row()
+            .unwrap()
+            .iter()
+            .map(BindingValue::value)
+            .collect::<Vec<_>>(),
+        [Some("Ada".to_string()), None]
+    );
+}
+
+#[test]
+fn derive_mergeable() {
+    #[derive(Mergeable)]
+    #[snowsql(table_name = "people")]
+    struct Person {
+        #[snowsql(key)]
+        id: i64,
+        name: String,
+        #[snowsql(skip_insert)]
+        #[allow(dead_code)]
+        updated_at: String,
+    }
+
+    assert_eq!(Person::MERGE_COLUMNS, ["id", "name"]);
+    assert_eq!(Person::MERGE_KEYS, ["id"]);
+
+    assert_eq!(
+        Person::merge_statement(1),
+        "MERGE INTO people AS target USING (SELECT ? AS id, ? AS name) AS source ON target.id = source.id WHEN MATCHED THEN UPDATE SET target.name = source.name WHEN NOT MATCHED THEN INSERT (id, name) VALUES (source.id, source.name)"
+    );
+    assert_eq!(
+        Person::merge_statement(2),
+        "MERGE INTO people AS target USING (SELECT column1 AS id, column2 AS name FROM VALUES (?, ?), (?, ?)) AS source ON target.id = source.id WHEN MATCHED THEN UPDATE SET target.name = source.name WHEN NOT MATCHED THEN INSERT (id, name) VALUES (source.id, source.name)"
+    );
+
+    let person = Person {
+        id: 7,
+        name: "Ada".into(),
+        updated_at: String::new(),
+    };
+
+    assert_eq!(
+        person
+            .merge_values()
+            .unwrap()
+            .iter()
+            .map(BindingValue::to_string)
+            .collect::<Vec<_>>(),
+        ["7", "Ada"]
+    );
+}
+
+#[test]
+fn derive_renamed_columns() {
+    #[allow(dead_code)]
+    #[derive(FromRow, Selectable, Insertable, Mergeable)]
+    #[snowsql(
+        table_name = "clients",
+        rename_all = "SCREAMING_SNAKE_CASE",
+        order_by = "client_id"
+    )]
+    struct Client {
+        #[snowsql(key)]
+        client_id: String,
+        #[snowsql(rename = "DisplayName")]
+        name: String,
+        #[snowsql(rename = "display name")]
+        label: String,
+        #[snowsql(rename = "lower_case")]
+        lower: String,
+    }
+
+    // names that are not upper-case are quoted, so Snowflake keeps them as written
+    assert_eq!(
+        Client::SELECT,
+        r#"CLIENT_ID, "DisplayName", "display name", "lower_case""#
+    );
+    assert_eq!(Client::ORDER_BY, "CLIENT_ID");
+    assert!(Client::select_all()
+        .as_statement()
+        .ends_with(" FROM clients ORDER BY CLIENT_ID"));
+    assert_eq!(
+        Client::INSERT,
+        r#"INSERT INTO clients (CLIENT_ID, "DisplayName", "display name", "lower_case") VALUES (?, ?, ?, ?)"#
+    );
+    assert_eq!(
+        Client::merge_statement(1),
+        r#"MERGE INTO clients AS target USING (SELECT ? AS CLIENT_ID, ? AS "DisplayName", ? AS "display name", ? AS "lower_case") AS source ON target.CLIENT_ID = source.CLIENT_ID WHEN MATCHED THEN UPDATE SET target."DisplayName" = source."DisplayName", target."display name" = source."display name", target."lower_case" = source."lower_case" WHEN NOT MATCHED THEN INSERT (CLIENT_ID, "DisplayName", "display name", "lower_case") VALUES (source.CLIENT_ID, source."DisplayName", source."display name", source."lower_case")"#
+    );
+
+    // rows are still read by the column names Snowflake returns
+    let schema = Client::SCHEMA
+        .columns
+        .iter()
+        .map(|col| col.name)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        schema,
+        ["CLIENT_ID", "DisplayName", "display name", "lower_case"]
+    );
+
+    #[allow(dead_code)]
+    #[derive(FromRow, Selectable)]
+    #[snowsql(table_name = "clients", rename_all = "camelCase")]
+    struct CamelClient {
+        #[snowsql(order_by)]
+        client_id: String,
+    }
+
+    assert_eq!(CamelClient::SELECT, r#""clientId""#);
+    assert_eq!(CamelClient::ORDER_BY, r#""clientId""#);
+    // ordered by the column, not a string literal
+    assert_eq!(
+        CamelClient::select_all().as_statement(),
+        r#"SELECT "clientId" FROM clients ORDER BY "clientId""#
+    );
+}
+
+#[test]
+fn renamed_column_in_errors() {
+    #[allow(dead_code)]
+    #[derive(Debug, FromRow)]
+    struct Client {
+        #[snowsql(rename = "CLIENT_ID")]
+        id: u32,
+    }
+
+    let err = serde_json::from_str::<snowsql::Row<Client>>(r#"["not a number"]"#)
+        .expect_err("deserializing invalid row");
+
+    assert!(err.to_string().contains("`CLIENT_ID`"), "{err}");
+}
+
+fn parse_tags(s: Option<&str>) -> snowsql::DeserializeResult<Vec<String>> {
+    Ok(s.map(|s| s.split(',').map(String::from).collect())
+        .unwrap_or_default())
+}
+
+#[test]
+fn derive_field_parsers() {
+    #[derive(Debug, FromRow)]
+    struct Post {
+        #[snowsql(with = "parse_tags")]
+        tags: Vec<String>,
+        #[snowsql(default)]
+        likes: u32,
+        #[snowsql(default)]
+        title: String,
+        #[snowsql(skip)]
+        cached: Option<String>,
+    }
+
+    let post = serde_json::from_str::<snowsql::Row<Post>>(r#"["a,b", null]"#)
+        .expect("deserializing row")
+        .0;
+
+    assert_eq!(post.tags, ["a", "b"]);
+    assert_eq!(post.likes, 0);
+    assert_eq!(post.title, "");
+    assert_eq!(post.cached, None);
+}